@@ -0,0 +1,42 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let fields = match &input.data {
+        | Data::Struct(data) => match &data.fields {
+            | Fields::Named(fields) => &fields.named,
+            | _ => return Err(syn::Error::new_spanned(ident, "`Bundle` can only be derived for structs with named fields")),
+        },
+        | _ => return Err(syn::Error::new_spanned(ident, "`Bundle` can only be derived for structs")),
+    };
+
+    let names = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+    let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ecs::archetype::ArchetypeDescriptor for #ident #ty_generics #where_clause {
+            fn layout() -> ::ecs::archetype::ArchetypeLayout {
+                let mut layout = ::ecs::archetype::ArchetypeLayout::default();
+                #(layout.extend(<#types as ::ecs::archetype::ArchetypeDescriptor>::layout());)*
+                layout
+            }
+        }
+
+        impl #impl_generics ::ecs::component::ComponentSource for #ident #ty_generics #where_clause {
+            fn add_to_layout(
+                &self,
+                layout: &mut ::ecs::archetype::ArchetypeLayout,
+            ) -> ::std::result::Result<(), ::ecs::error::EcsError> {
+                #(::ecs::component::ComponentSource::add_to_layout(&self.#names, layout)?;)*
+                Ok(())
+            }
+
+            fn write_components(self, inserter: &mut ::ecs::insert::EntityInserter<'_>) {
+                #(::ecs::component::ComponentSource::write_components(self.#names, inserter);)*
+            }
+        }
+    })
+}
@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitStr, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let mut storage = quote!(VecStorage);
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("component")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("storage") {
+                return Err(meta.error("unknown `component` attribute"));
+            }
+
+            let value = meta.value()?.parse::<LitStr>()?;
+
+            storage = match value.value().as_str() {
+                | "vec" => quote!(VecStorage),
+                | "single" => quote!(SingleStorage),
+                | "null" => quote!(NullStorage),
+                | _ => return Err(syn::Error::new_spanned(value, "expected \"vec\", \"single\" or \"null\"")),
+            };
+
+            Ok(())
+        })?;
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ecs::component::Component for #ident #ty_generics #where_clause {
+            type Storage = ::ecs::storage::#storage<Self>;
+        }
+    })
+}
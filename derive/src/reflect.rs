@@ -0,0 +1,173 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Fields, GenericParam, Ident, Index, Result};
+
+struct Field {
+    name: String,
+    binding: Ident,
+    access: TokenStream,
+}
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(::ecs::reflect::Reflect));
+        }
+    }
+
+    let (kind, variant, fields, field, field_mut) = match &input.data {
+        | Data::Struct(data) => {
+            let fields = collect(&data.fields)?;
+            let names = fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+            let access = fields.iter().map(|f| &f.access).collect::<Vec<_>>();
+
+            (
+                quote!(Struct),
+                quote!(None),
+                quote!(&[#(#names),*]),
+                quote!(match name {
+                    #(#names => Some(&self.#access),)*
+                    _ => None,
+                }),
+                quote!(match name {
+                    #(#names => Some(&mut self.#access),)*
+                    _ => None,
+                }),
+            )
+        },
+        | Data::Enum(data) => {
+            let mut variants = Vec::new();
+            let mut fields = Vec::new();
+            let mut lookups = Vec::new();
+
+            for v in &data.variants {
+                let name = &v.ident;
+                let label = name.to_string();
+                let reflected = collect(&v.fields)?;
+                let names = reflected.iter().map(|f| &f.name).collect::<Vec<_>>();
+                let bindings = reflected.iter().map(|f| &f.binding).collect::<Vec<_>>();
+                let pattern = match &v.fields {
+                    | Fields::Named(_) => {
+                        let access = reflected.iter().map(|f| &f.access);
+                        quote!(Self::#name { #(#access: #bindings,)* .. })
+                    },
+                    | Fields::Unnamed(unnamed) => {
+                        let slots = (0..unnamed.unnamed.len()).map(|i| {
+                            match reflected.iter().find(|f| f.name == i.to_string()) {
+                                | Some(f) => {
+                                    let binding = &f.binding;
+                                    quote!(#binding)
+                                },
+                                | None => quote!(_),
+                            }
+                        });
+                        quote!(Self::#name(#(#slots),*))
+                    },
+                    | Fields::Unit => quote!(Self::#name),
+                };
+
+                variants.push(quote!(Self::#name { .. } => #label));
+                fields.push(quote!(Self::#name { .. } => &[#(#names),*]));
+                lookups.push(quote!(#pattern => match name {
+                    #(#names => Some(#bindings),)*
+                    _ => None,
+                }));
+            }
+
+            (
+                quote!(Enum),
+                quote!(Some(match self { #(#variants,)* })),
+                quote!(match self { #(#fields,)* }),
+                quote!(match self { #(#lookups,)* }),
+                quote!(match self { #(#lookups,)* }),
+            )
+        },
+        | Data::Union(_) => return Err(syn::Error::new_spanned(ident, "`Reflect` cannot be derived for unions")),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ecs::reflect::Reflect for #ident #ty_generics #where_clause {
+            fn type_name(&self) -> &'static str {
+                ::std::any::type_name::<Self>()
+            }
+
+            fn kind(&self) -> ::ecs::reflect::ReflectKind {
+                ::ecs::reflect::ReflectKind::#kind
+            }
+
+            fn as_any(&self) -> &dyn ::std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn ::std::any::Any {
+                self
+            }
+
+            fn variant(&self) -> Option<&'static str> {
+                #variant
+            }
+
+            fn fields(&self) -> &'static [&'static str] {
+                #fields
+            }
+
+            fn field(&self, name: &str) -> Option<&dyn ::ecs::reflect::Reflect> {
+                #field
+            }
+
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn ::ecs::reflect::Reflect> {
+                #field_mut
+            }
+        }
+    })
+}
+
+fn collect(fields: &Fields) -> Result<Vec<Field>> {
+    let mut reflected = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        if ignored(&field.attrs)? {
+            continue;
+        }
+
+        reflected.push(match &field.ident {
+            | Some(ident) => Field {
+                name: ident.to_string(),
+                binding: format_ident!("__{}", ident),
+                access: quote!(#ident),
+            },
+            | None => {
+                let index = Index::from(i);
+
+                Field {
+                    name: i.to_string(),
+                    binding: format_ident!("__{}", i),
+                    access: quote!(#index),
+                }
+            },
+        });
+    }
+
+    Ok(reflected)
+}
+
+fn ignored(attrs: &[Attribute]) -> Result<bool> {
+    let mut ignore = false;
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("reflect")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ignore") {
+                ignore = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `reflect` attribute"))
+            }
+        })?;
+    }
+
+    Ok(ignore)
+}
@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, GenericParam, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let fields = match &input.data {
+        | Data::Struct(data) => match &data.fields {
+            | Fields::Named(fields) => &fields.named,
+            | _ => {
+                return Err(syn::Error::new_spanned(ident, "`SystemParam` can only be derived for structs with named fields"))
+            },
+        },
+        | _ => return Err(syn::Error::new_spanned(ident, "`SystemParam` can only be derived for structs")),
+    };
+
+    let mut args = Vec::new();
+
+    for param in &input.generics.params {
+        args.push(match param {
+            | GenericParam::Lifetime(def) if def.lifetime.ident == "world" => quote!('__world),
+            | GenericParam::Lifetime(def) if def.lifetime.ident == "resources" => quote!('__resources),
+            | GenericParam::Lifetime(def) => {
+                return Err(syn::Error::new_spanned(def, "expected lifetimes named `'world` or `'resources`"))
+            },
+            | GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                quote!(#ident)
+            },
+            | GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote!(#ident)
+            },
+        });
+    }
+
+    let names = fields.iter().map(|f| &f.ident);
+    let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!('__world));
+    generics.params.push(parse_quote!('__resources));
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ecs::system::SystemParam<'__world, '__resources> for #ident #ty_generics #where_clause {
+            type Result = #ident<#(#args),*>;
+
+            unsafe fn fetch_unchecked(
+                world: &'__world ::ecs::world::World,
+                resources: &'__resources ::ecs::resource::Resources,
+            ) -> Self::Result {
+                #ident {
+                    #(#names: <#types as ::ecs::system::SystemParam<'__world, '__resources>>::fetch_unchecked(world, resources),)*
+                }
+            }
+
+            fn access(access: &mut ::ecs::query::QueryAccess) {
+                #(<#types as ::ecs::system::SystemParam<'__world, '__resources>>::access(access);)*
+            }
+        }
+    })
+}
@@ -1,7 +1,8 @@
-use crate::component::Component;
+use crate::component::{Component, ComponentId};
 use crate::entity::Entity;
-use crate::storage::{AnyArchetypeStorage, ArchetypeStorage};
-use std::any::TypeId;
+use crate::storage::{AnyArchetypeStorage, ArchetypeStorage, BlobArchetypeStorage};
+use crate::error::EcsError;
+use std::any::type_name;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -17,7 +18,7 @@ pub struct Archetype {
 
 #[derive(Default, Debug, Clone)]
 pub struct ArchetypeLayout {
-    pub components: Vec<TypeId>,
+    pub components: Vec<ComponentId>,
     pub constructors: Vec<fn() -> Box<dyn AnyArchetypeStorage>>,
 }
 
@@ -25,6 +26,26 @@ pub trait ArchetypeDescriptor {
     fn layout() -> ArchetypeLayout;
 }
 
+impl<T: Component> ArchetypeDescriptor for T {
+    fn layout() -> ArchetypeLayout {
+        let mut layout = ArchetypeLayout::default();
+        layout.add::<T>();
+        layout
+    }
+}
+
+impl ArchetypeDescriptor for () {
+    fn layout() -> ArchetypeLayout {
+        ArchetypeLayout::default()
+    }
+}
+
+impl<T: ArchetypeDescriptor> ArchetypeDescriptor for Option<T> {
+    fn layout() -> ArchetypeLayout {
+        ArchetypeLayout::default()
+    }
+}
+
 impl Archetype {
     pub fn new(index: ArchetypeIndex, layout: ArchetypeLayout) -> Self {
         Self {
@@ -37,20 +58,51 @@ impl Archetype {
 
 impl ArchetypeLayout {
     pub fn add<T: Component>(&mut self) {
-        let ty = TypeId::of::<T>();
+        self.try_add::<T>().unwrap_or_else(|err| panic!("{}", err));
+    }
+
+    pub fn try_add<T: Component>(&mut self) -> Result<(), EcsError> {
+        let ty = ComponentId::of::<T>();
+
+        if self.components.contains(&ty) {
+            return Err(EcsError::DuplicateComponent {
+                component: type_name::<T>().to_owned(),
+            });
+        }
 
-        assert!(!self.components.contains(&ty));
         self.components.push(ty);
         self.constructors.push(ArchetypeStorage::<T>::any);
+        Ok(())
     }
 
-    pub fn add_any(&mut self, ty: TypeId, ctor: fn() -> Box<dyn AnyArchetypeStorage>) {
+    pub fn add_any(&mut self, ty: ComponentId, ctor: fn() -> Box<dyn AnyArchetypeStorage>) {
         assert!(!self.components.contains(&ty));
         self.components.push(ty);
         self.constructors.push(ctor);
     }
 
-    pub fn contains(&self, components: &[TypeId]) -> bool {
+    pub fn extend(&mut self, other: ArchetypeLayout) {
+        for (ty, ctor) in other.components.into_iter().zip(other.constructors) {
+            self.add_any(ty, ctor);
+        }
+    }
+
+    pub fn add_dynamic(&mut self, ty: ComponentId) {
+        self.add_any(ty, BlobArchetypeStorage::unregistered);
+    }
+
+    pub fn remove(&mut self, ty: ComponentId) -> bool {
+        match self.components.iter().position(|&t| t == ty) {
+            | Some(idx) => {
+                self.components.remove(idx);
+                self.constructors.remove(idx);
+                true
+            },
+            | None => false,
+        }
+    }
+
+    pub fn contains(&self, components: &[ComponentId]) -> bool {
         components.iter().all(|t| self.components.contains(t))
     }
 }
@@ -82,10 +134,10 @@ macro_rules! impl_archetype_descriptor {
     };
 
     (@impl $($ty:ident),+) => {
-        impl<$($ty: Component),+> ArchetypeDescriptor for ($($ty,)+) {
+        impl<$($ty: ArchetypeDescriptor),+> ArchetypeDescriptor for ($($ty,)+) {
             fn layout() -> ArchetypeLayout {
                 let mut layout = ArchetypeLayout::default();
-                $(layout.add::<$ty>();)+
+                $(layout.extend($ty::layout());)+
                 layout
             }
         }
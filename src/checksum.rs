@@ -0,0 +1,235 @@
+use crate::archetype::{Archetype, ArchetypeIndex};
+use crate::component::ComponentIndex;
+use crate::entity::Entity;
+use crate::error::EcsError;
+use crate::storage::AnyArchetypeStorage;
+use crate::world::World;
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+
+pub(crate) type HashFn = fn(&dyn Any, &mut dyn Hasher);
+type BlobHashFn = unsafe fn(*const u8, &mut dyn Hasher);
+
+pub struct ChecksumHasher(u64);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorldDiff {
+    pub removed: Vec<Entity>,
+    pub spawned: Vec<Entity>,
+    pub components: Vec<ComponentDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDiff {
+    pub entity: Entity,
+    pub component: String,
+    pub kind: DiffKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Removed,
+    Added,
+    Changed,
+}
+
+struct Column<'world> {
+    name: &'world str,
+    storage: &'world dyn AnyArchetypeStorage,
+    hash: ColumnHash,
+}
+
+#[derive(Clone, Copy)]
+enum ColumnHash {
+    Typed(HashFn),
+    Blob(BlobHashFn),
+}
+
+impl Default for ChecksumHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for ChecksumHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+}
+
+impl WorldDiff {
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.spawned.is_empty() && self.components.is_empty()
+    }
+}
+
+impl<'world> Column<'world> {
+    fn hash(&self, archetype: ArchetypeIndex, component: ComponentIndex, hasher: &mut dyn Hasher) {
+        match self.hash {
+            | ColumnHash::Typed(hash) => {
+                if let Some(any) = self.storage.get_any(archetype, component) {
+                    hash(any, hasher);
+                }
+            },
+            | ColumnHash::Blob(hash) => {
+                if let Some(ptr) = self.storage.get_ptr(archetype, component) {
+                    unsafe { hash(ptr.as_ptr(), hasher) };
+                }
+            },
+        }
+    }
+
+    fn checksum(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> u64 {
+        let mut hasher = ChecksumHasher::default();
+
+        self.hash(archetype, component, &mut hasher);
+        hasher.finish()
+    }
+}
+
+impl World {
+    /// Hashes every entity and component. Typed components need [`World::register_hash`] and dynamic ones a
+    /// [`ComponentDescriptor::with_hash`](crate::component::ComponentDescriptor::with_hash); any other component makes
+    /// this return [`EcsError::NotHashable`].
+    pub fn checksum(&self) -> Result<u64, EcsError> {
+        let columns = self.archetypes.iter().map(|a| self.columns(a)).collect::<Result<Vec<_>, _>>()?;
+        let mut hasher = ChecksumHasher::default();
+
+        for (entity, archetype, component) in self.sorted_entities() {
+            hasher.write_u64(entity.0);
+
+            for column in &columns[archetype.0 as usize] {
+                column.name.hash(&mut hasher);
+                column.hash(archetype, component, &mut hasher);
+            }
+        }
+
+        Ok(hasher.finish())
+    }
+
+    pub fn diff(&self, other: &World) -> Result<WorldDiff, EcsError> {
+        let columns = self.archetypes.iter().map(|a| self.columns(a)).collect::<Result<Vec<_>, _>>()?;
+        let other_columns = other
+            .archetypes
+            .iter()
+            .map(|a| other.columns(a))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut diff = WorldDiff::default();
+
+        for (entity, archetype, component) in self.sorted_entities() {
+            let data = match other.entities.get(entity) {
+                | Some(data) => data,
+                | None => {
+                    diff.removed.push(entity);
+                    continue;
+                },
+            };
+            let left = &columns[archetype.0 as usize];
+            let right = &other_columns[data.archetype().0 as usize];
+
+            for column in left {
+                let kind = match right.iter().find(|c| c.name == column.name) {
+                    | None => DiffKind::Removed,
+                    | Some(c) if c.checksum(data.archetype(), data.component()) != column.checksum(archetype, component) => {
+                        DiffKind::Changed
+                    },
+                    | Some(_) => continue,
+                };
+
+                diff.components.push(ComponentDiff {
+                    entity,
+                    component: column.name.to_owned(),
+                    kind,
+                });
+            }
+
+            for column in right.iter().filter(|c| !left.iter().any(|l| l.name == c.name)) {
+                diff.components.push(ComponentDiff {
+                    entity,
+                    component: column.name.to_owned(),
+                    kind: DiffKind::Added,
+                });
+            }
+        }
+
+        for (entity, _, _) in other.sorted_entities() {
+            if !self.contains(entity) {
+                diff.spawned.push(entity);
+            }
+        }
+
+        Ok(diff)
+    }
+
+    fn sorted_entities(&self) -> Vec<(Entity, ArchetypeIndex, ComponentIndex)> {
+        let mut entities = self
+            .archetypes
+            .iter()
+            .flat_map(|a| {
+                a.entities
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, &e)| (e, a.index, ComponentIndex(i as u32)))
+            })
+            .collect::<Vec<_>>();
+
+        entities.sort_unstable_by_key(|&(e, _, _)| e.0);
+        entities
+    }
+
+    fn columns(&self, archetype: &Archetype) -> Result<Vec<Column<'_>>, EcsError> {
+        let mut columns = archetype
+            .layout
+            .components
+            .iter()
+            .map(|&id| {
+                let storage = self.components.get_any(id).unwrap();
+                let hash = self
+                    .registry()
+                    .get(id)
+                    .and_then(|info| info.hash_fn())
+                    .map(ColumnHash::Typed)
+                    .or_else(|| storage.as_blob()?.descriptor().hash_fn().map(ColumnHash::Blob))
+                    .ok_or_else(|| EcsError::NotHashable {
+                        component: storage.component_name().to_owned(),
+                    })?;
+
+                Ok(Column {
+                    name: storage.component_name(),
+                    storage,
+                    hash,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        columns.sort_unstable_by_key(|c| c.name);
+        Ok(columns)
+    }
+}
@@ -1,23 +1,174 @@
-use crate::archetype::ArchetypeDescriptor;
+use crate::archetype::{ArchetypeDescriptor, ArchetypeLayout};
+use crate::error::EcsError;
 use crate::insert::EntityInserter;
-use crate::storage::{Storage, VecStorage};
+use crate::storage::Storage;
+use std::alloc::Layout;
+use std::any::TypeId;
+use std::hash::Hasher;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct ComponentIndex(pub(crate) u32);
 
-pub trait Component: Sized + 'static {
-    type Storage: for<'a> Storage<'a, Self>;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ComponentId {
+    Type(TypeId),
+    Dynamic(u32),
 }
 
+#[derive(Debug, Clone)]
+pub struct ComponentDescriptor {
+    name: String,
+    layout: Layout,
+    drop: Option<unsafe fn(*mut u8)>,
+    hash: Option<unsafe fn(*const u8, &mut dyn Hasher)>,
+}
+
+/// Types stored as components must opt in, either with `#[derive(Component)]`, which can also pick the storage, or
+/// with [`impl_component!`](crate::impl_component), which uses [`VecStorage`](crate::storage::VecStorage) like the
+/// old blanket impl did:
+///
+/// ```
+/// use ecs::world::World;
+///
+/// struct Pos(u32);
+/// struct Vel(u32);
+///
+/// ecs::impl_component!(Pos, Vel);
+///
+/// World::default().create((Pos(0), Vel(1)));
+/// ```
+pub trait Component: Sized + Send + Sync + 'static {
+    type Storage: for<'a> Storage<'a, Self> + Send + Sync;
+}
+
+pub use ecs_derive::{Bundle, Component};
+
 pub trait ComponentSource: ArchetypeDescriptor {
-    fn insert_components(self, inserter: &mut EntityInserter<'_>);
+    fn add_to_layout(&self, layout: &mut ArchetypeLayout) -> Result<(), EcsError>;
+    fn write_components(self, inserter: &mut EntityInserter<'_>);
+
+    fn layout(&self) -> Result<ArchetypeLayout, EcsError> {
+        let mut layout = ArchetypeLayout::default();
+
+        self.add_to_layout(&mut layout)?;
+        Ok(layout)
+    }
+
+    fn insert_components(self, inserter: &mut EntityInserter<'_>)
+    where
+        Self: Sized,
+    {
+        self.write_components(inserter);
+        inserter.finish_entity();
+    }
+}
+
+impl<T: Component> ComponentSource for T {
+    fn add_to_layout(&self, layout: &mut ArchetypeLayout) -> Result<(), EcsError> {
+        layout.try_add::<T>()
+    }
+
+    fn write_components(self, inserter: &mut EntityInserter<'_>) {
+        inserter.component::<T>().extend(std::iter::once(self));
+    }
 }
 
-impl<T: 'static> Component for T {
-    type Storage = VecStorage<Self>;
+impl ComponentSource for () {
+    fn add_to_layout(&self, _: &mut ArchetypeLayout) -> Result<(), EcsError> {
+        Ok(())
+    }
+
+    fn write_components(self, _: &mut EntityInserter<'_>) {
+    }
+}
+
+impl<T: ComponentSource> ComponentSource for Option<T> {
+    fn add_to_layout(&self, layout: &mut ArchetypeLayout) -> Result<(), EcsError> {
+        match self {
+            | Some(source) => source.add_to_layout(layout),
+            | None => Ok(()),
+        }
+    }
+
+    fn write_components(self, inserter: &mut EntityInserter<'_>) {
+        if let Some(source) = self {
+            source.write_components(inserter);
+        }
+    }
+}
+
+impl ComponentId {
+    pub fn of<T: Component>() -> Self {
+        ComponentId::Type(TypeId::of::<T>())
+    }
+
+    pub(crate) fn next_dynamic() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+
+        ComponentId::Dynamic(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, ComponentId::Dynamic(_))
+    }
 }
 
+impl ComponentDescriptor {
+    pub fn new<N: Into<String>>(name: N, layout: Layout) -> Self {
+        Self {
+            name: name.into(),
+            layout,
+            drop: None,
+            hash: None,
+        }
+    }
+
+    pub fn with_drop(mut self, drop: unsafe fn(*mut u8)) -> Self {
+        self.drop = Some(drop);
+        self
+    }
+
+    /// Hashes a value for [`World::checksum`](crate::world::World::checksum). Raw bytes may include padding, so
+    /// dynamic components without a hash fn are not hashable.
+    pub fn with_hash(mut self, hash: unsafe fn(*const u8, &mut dyn Hasher)) -> Self {
+        self.hash = Some(hash);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn drop_fn(&self) -> Option<unsafe fn(*mut u8)> {
+        self.drop
+    }
+
+    pub fn hash_fn(&self) -> Option<unsafe fn(*const u8, &mut dyn Hasher)> {
+        self.hash
+    }
+}
+
+#[macro_export]
+macro_rules! impl_component {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::component::Component for $ty {
+                type Storage = $crate::storage::VecStorage<Self>;
+            }
+        )+
+    };
+}
+
+impl_component!(
+    bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, String, &'static str
+);
+
 macro_rules! impl_component_source {
     ($head:ident) => {
         impl_component_source!(@impl $head);
@@ -29,17 +180,20 @@ macro_rules! impl_component_source {
     };
 
     (@impl $($ty:ident),+) => {
-        impl<$($ty: Component),+> ComponentSource for ($($ty,)+) {
+        impl<$($ty: ComponentSource),+> ComponentSource for ($($ty,)+) {
             #[allow(non_snake_case)]
-            fn insert_components(self, inserter: &mut EntityInserter<'_>) {
+            fn add_to_layout(&self, layout: &mut ArchetypeLayout) -> Result<(), EcsError> {
                 let ($($ty,)+) = self;
 
-                $(
-                    let mut edit = inserter.component::<$ty>();
-                    edit.extend(std::iter::once($ty));
-                )+
+                $($ty.add_to_layout(layout)?;)+
+                Ok(())
+            }
+
+            #[allow(non_snake_case)]
+            fn write_components(self, inserter: &mut EntityInserter<'_>) {
+                let ($($ty,)+) = self;
 
-                inserter.finish_entity();
+                $($ty.write_components(inserter);)+
             }
         }
     };
@@ -48,3 +202,41 @@ macro_rules! impl_component_source {
 impl_component_source!(
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z
 );
+
+#[cfg(doctest)]
+/// ```compile_fail,E0277
+/// use ecs::component::Component;
+/// use std::rc::Rc;
+///
+/// #[derive(Component)]
+/// struct Shared(Rc<u32>);
+/// ```
+///
+/// ```
+/// use ecs::component::Component;
+/// use std::sync::Arc;
+///
+/// #[derive(Component)]
+/// struct Shared(Arc<u32>);
+/// ```
+struct ComponentsAreSendAndSync;
+
+#[cfg(doctest)]
+/// ```compile_fail,E0277
+/// use ecs::world::World;
+///
+/// struct Plain(u32);
+///
+/// World::default().create((Plain(1),));
+/// ```
+///
+/// ```
+/// use ecs::component::Component;
+/// use ecs::world::World;
+///
+/// #[derive(Component)]
+/// struct Plain(u32);
+///
+/// World::default().create((Plain(1), 2u32, "name"));
+/// ```
+struct ComponentsMustBeDerived;
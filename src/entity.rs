@@ -78,6 +78,8 @@ impl EntityMap {
 
     pub fn remove(&mut self, entity: Entity) -> Option<EntityData> {
         if self.contains(entity) {
+            self.free.insert(entity.0);
+
             Some(unsafe {
                 std::mem::replace(&mut self.entities[entity.0 as usize], MaybeUninit::uninit())
                     .assume_init()
@@ -0,0 +1,86 @@
+use crate::component::ComponentId;
+use crate::entity::Entity;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EcsError {
+    NoSuchEntity(Entity),
+    MissingComponent { entity: Entity, component: String },
+    DuplicateComponent { component: String },
+    ComponentBorrowed { component: String },
+    UnregisteredComponent { component: ComponentId },
+    SizeMismatch { component: String, expected: usize, found: usize },
+    StorageFull { component: String },
+    NotCloneable { component: String },
+    NotReplicated { component: String },
+    NotHashable { component: String },
+    MalformedDelta { reason: &'static str },
+    QueryMismatch { entity: Entity, query: &'static str },
+    DuplicateEntity(Entity),
+    NoMatchingEntity { query: &'static str },
+    MultipleMatchingEntities { query: &'static str, count: usize },
+    MissingResource { resource: &'static str },
+    ResourceBorrowed { resource: &'static str },
+    NonSendAccess { resource: &'static str },
+    ConflictingAccess { component: String },
+    ThreadLocalSystem { system: &'static str },
+    RelationCycle { entity: Entity, relation: &'static str },
+    MissingIndex { index: &'static str },
+    StaleIndex { index: &'static str },
+}
+
+impl fmt::Display for EcsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            | EcsError::NoSuchEntity(entity) => write!(f, "entity {:?} does not exist", entity),
+            | EcsError::MissingComponent { entity, component } => {
+                write!(f, "entity {:?} has no component `{}`", entity, component)
+            },
+            | EcsError::DuplicateComponent { component } => {
+                write!(f, "component `{}` appears more than once in bundle", component)
+            },
+            | EcsError::ComponentBorrowed { component } => write!(f, "component `{}` is already borrowed", component),
+            | EcsError::UnregisteredComponent { component } => write!(f, "component {:?} is not registered", component),
+            | EcsError::SizeMismatch { component, expected, found } => write!(
+                f,
+                "component `{}` expects {} bytes but {} were given",
+                component, expected, found
+            ),
+            | EcsError::StorageFull { component } => write!(f, "storage for component `{}` is full", component),
+            | EcsError::NotCloneable { component } => write!(f, "component `{}` cannot be cloned", component),
+            | EcsError::NotReplicated { component } => write!(f, "component `{}` is not replicated", component),
+            | EcsError::NotHashable { component } => write!(f, "component `{}` has no registered hash", component),
+            | EcsError::MalformedDelta { reason } => write!(f, "malformed delta: {}", reason),
+            | EcsError::QueryMismatch { entity, query } => {
+                write!(f, "entity {:?} does not match query `{}`", entity, query)
+            },
+            | EcsError::DuplicateEntity(entity) => write!(f, "entity {:?} was requested more than once", entity),
+            | EcsError::NoMatchingEntity { query } => write!(f, "no entity matches query `{}`", query),
+            | EcsError::MultipleMatchingEntities { query, count } => {
+                write!(f, "{} entities match query `{}` but exactly one was expected", count, query)
+            },
+            | EcsError::MissingResource { resource } => write!(f, "resource `{}` not available", resource),
+            | EcsError::ResourceBorrowed { resource } => write!(f, "resource `{}` is already borrowed", resource),
+            | EcsError::NonSendAccess { resource } => {
+                write!(f, "non-send resource `{}` accessed from a thread other than its owner", resource)
+            },
+            | EcsError::ConflictingAccess { component } => {
+                write!(f, "conflicting access to component `{}` in system", component)
+            },
+            | EcsError::ThreadLocalSystem { system } => {
+                write!(f, "thread-local system `{}` run on a thread other than its resources' owner", system)
+            },
+            | EcsError::RelationCycle { entity, relation } => {
+                write!(f, "entity {:?} is reached again through relation `{}`", entity, relation)
+            },
+            | EcsError::MissingIndex { index } => write!(f, "index `{}` is not registered", index),
+            | EcsError::StaleIndex { index } => {
+                write!(f, "index `{}` is out of date and borrowed elsewhere, so it cannot be refreshed", index)
+            },
+        }
+    }
+}
+
+impl Error for EcsError {
+}
@@ -1,9 +1,8 @@
-use crate::component as c;
-use std::any::TypeId;
+use crate::component::{self as c, ComponentId};
 use std::marker::PhantomData;
 
 pub trait LayoutFilter {
-    fn matches(&self, components: &[TypeId]) -> bool;
+    fn matches(&self, components: &[ComponentId]) -> bool;
 }
 
 pub struct Any;
@@ -13,20 +12,20 @@ pub struct Not<T>(T);
 pub struct Component<T: c::Component>(PhantomData<T>);
 
 impl LayoutFilter for Any {
-    fn matches(&self, _: &[TypeId]) -> bool {
+    fn matches(&self, _: &[ComponentId]) -> bool {
         true
     }
 }
 
 impl<T: LayoutFilter> LayoutFilter for Not<T> {
-    fn matches(&self, components: &[TypeId]) -> bool {
+    fn matches(&self, components: &[ComponentId]) -> bool {
         !self.0.matches(components)
     }
 }
 
 impl<T: c::Component> LayoutFilter for Component<T> {
-    fn matches(&self, components: &[TypeId]) -> bool {
-        components.contains(&TypeId::of::<T>())
+    fn matches(&self, components: &[ComponentId]) -> bool {
+        components.contains(&ComponentId::of::<T>())
     }
 }
 
@@ -62,7 +61,7 @@ macro_rules! impl_tuple {
     (@impl $($ty:ident),*) => {
         impl<$($ty: LayoutFilter),*> LayoutFilter for And<($($ty,)*)> {
             #[allow(non_snake_case, unused_variables)]
-            fn matches(&self, components: &[TypeId]) -> bool {
+            fn matches(&self, components: &[ComponentId]) -> bool {
                 let Self(($($ty,)*)) = self;
                 $($ty.matches(components) &&)* true
             }
@@ -70,7 +69,7 @@ macro_rules! impl_tuple {
 
         impl<$($ty: LayoutFilter),*> LayoutFilter for Or<($($ty,)*)> {
             #[allow(non_snake_case, unused_variables)]
-            fn matches(&self, components: &[TypeId]) -> bool {
+            fn matches(&self, components: &[ComponentId]) -> bool {
                 let Self(($($ty,)*)) = self;
                 $($ty.matches(components) ||)* true
             }
@@ -0,0 +1,79 @@
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::world::World;
+use std::sync::Arc;
+
+pub type Hook = Arc<dyn Fn(&mut DeferredWorld<'_>, Entity) + Send + Sync>;
+type Command = Box<dyn FnOnce(&mut World)>;
+
+#[derive(Default, Clone)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<Hook>,
+    pub(crate) on_insert: Option<Hook>,
+    pub(crate) on_remove: Option<Hook>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HookKind {
+    Add,
+    Insert,
+    Remove,
+}
+
+pub struct DeferredWorld<'w> {
+    world: &'w mut World,
+    commands: &'w mut CommandQueue,
+}
+
+#[derive(Default)]
+pub struct CommandQueue {
+    commands: Vec<Command>,
+}
+
+impl ComponentHooks {
+    pub(crate) fn get(&self, kind: HookKind) -> Option<&Hook> {
+        match kind {
+            | HookKind::Add => self.on_add.as_ref(),
+            | HookKind::Insert => self.on_insert.as_ref(),
+            | HookKind::Remove => self.on_remove.as_ref(),
+        }
+    }
+}
+
+impl<'w> DeferredWorld<'w> {
+    pub(crate) fn new(world: &'w mut World, commands: &'w mut CommandQueue) -> Self {
+        Self { world, commands }
+    }
+
+    pub fn world(&self) -> &World {
+        self.world
+    }
+
+    pub fn component<T: Component>(&self, entity: Entity) -> Option<&T> {
+        self.world.component(entity)
+    }
+
+    pub fn component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        self.world.component_mut(entity)
+    }
+
+    pub fn defer<F: FnOnce(&mut World) + 'static>(&mut self, command: F) {
+        self.commands.push(command);
+    }
+}
+
+impl CommandQueue {
+    pub fn push<F: FnOnce(&mut World) + 'static>(&mut self, command: F) {
+        self.commands.push(Box::new(command));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn apply(&mut self, world: &mut World) {
+        for command in std::mem::take(&mut self.commands) {
+            command(world);
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use crate::component::{Component, ComponentId, ComponentIndex};
+use crate::entity::Entity;
+use crate::error::EcsError;
+use crate::hook::HookKind;
+use crate::storage::{change_tick, AnyArchetypeStorage, Storage};
+use crate::subworld::AnyWorld;
+use crate::world::{StorageAccess, World};
+use std::any::{type_name, Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{Deref, RangeBounds};
+use std::sync::{RwLock, RwLockReadGuard, TryLockError};
+
+pub struct Index<C: Component, K, S> {
+    key: fn(&C) -> K,
+    keys: HashMap<Entity, K>,
+    store: S,
+    tick: u64,
+}
+
+pub type UniqueIndex<C, K> = Index<C, K, UniqueStore<K>>;
+pub type RangeIndex<C, K> = Index<C, K, BTreeMap<K, Vec<Entity>>>;
+
+pub struct UniqueStore<K> {
+    entities: HashMap<K, Entity>,
+    collisions: HashMap<K, Vec<Entity>>,
+}
+
+pub trait IndexStore<K>: Default + Send + Sync + 'static {
+    fn insert(&mut self, key: K, entity: Entity);
+    fn remove(&mut self, key: &K, entity: Entity);
+    fn clear(&mut self);
+}
+
+pub trait AnyIndex: Any + Send + Sync {
+    fn component(&self) -> ComponentId;
+    fn insert(&mut self, access: StorageAccess<'_>, entity: Entity);
+    fn remove(&mut self, entity: Entity);
+    fn refresh(&mut self, access: StorageAccess<'_>);
+    fn is_stale(&self, access: StorageAccess<'_>) -> bool;
+    fn invalidate(&mut self);
+}
+
+#[derive(Default)]
+pub(crate) struct Indexes {
+    indexes: HashMap<TypeId, RwLock<Box<dyn AnyIndex>>>,
+}
+
+pub struct IndexRef<'a, I> {
+    guard: RwLockReadGuard<'a, Box<dyn AnyIndex>>,
+    _marker: PhantomData<&'a I>,
+}
+
+impl<K> Default for UniqueStore<K> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::new(),
+            collisions: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone + Send + Sync + 'static> IndexStore<K> for UniqueStore<K> {
+    fn insert(&mut self, key: K, entity: Entity) {
+        match self.entities.get(&key) {
+            | Some(&owner) if owner != entity => self.collisions.entry(key).or_default().push(entity),
+            | Some(_) => {},
+            | None => {
+                self.entities.insert(key, entity);
+            },
+        }
+    }
+
+    fn remove(&mut self, key: &K, entity: Entity) {
+        let collisions = match self.collisions.get_mut(key) {
+            | Some(collisions) => collisions,
+            | None => {
+                if self.entities.get(key) == Some(&entity) {
+                    self.entities.remove(key);
+                }
+
+                return;
+            },
+        };
+
+        if self.entities.get(key) == Some(&entity) {
+            self.entities.insert(key.clone(), collisions.remove(0));
+        } else {
+            collisions.retain(|&e| e != entity);
+        }
+
+        if collisions.is_empty() {
+            self.collisions.remove(key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entities.clear();
+        self.collisions.clear();
+    }
+}
+
+impl<K: Ord + Send + Sync + 'static> IndexStore<K> for BTreeMap<K, Vec<Entity>> {
+    fn insert(&mut self, key: K, entity: Entity) {
+        self.entry(key).or_default().push(entity);
+    }
+
+    fn remove(&mut self, key: &K, entity: Entity) {
+        if let Some(entities) = self.get_mut(key) {
+            entities.retain(|&e| e != entity);
+
+            if entities.is_empty() {
+                BTreeMap::remove(self, key);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::clear(self);
+    }
+}
+
+impl<C: Component, K: Eq + Clone, S: IndexStore<K>> Index<C, K, S> {
+    pub fn new(key: fn(&C) -> K) -> Self {
+        Self {
+            key,
+            keys: HashMap::new(),
+            store: S::default(),
+            tick: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn key(&self, entity: Entity) -> Option<&K> {
+        self.keys.get(&entity)
+    }
+
+    fn set(&mut self, entity: Entity, key: K) {
+        match self.keys.insert(entity, key.clone()) {
+            | Some(old) if old == key => return,
+            | Some(old) => self.store.remove(&old, entity),
+            | None => {},
+        }
+
+        self.store.insert(key, entity);
+    }
+}
+
+impl<C: Component, K: Hash + Eq + Clone + Send + Sync + 'static> Index<C, K, UniqueStore<K>> {
+    pub fn get(&self, key: &K) -> Option<Entity> {
+        self.store.entities.get(key).copied()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.store.entities.contains_key(key)
+    }
+
+    pub fn has_collisions(&self) -> bool {
+        !self.store.collisions.is_empty()
+    }
+
+    pub fn collisions(&self) -> impl Iterator<Item = (&K, &[Entity])> + '_ {
+        self.store.collisions.iter().map(|(key, entities)| (key, entities.as_slice()))
+    }
+}
+
+impl<C: Component, K: Ord + Clone + Send + Sync + 'static> Index<C, K, BTreeMap<K, Vec<Entity>>> {
+    pub fn get(&self, key: &K) -> &[Entity] {
+        self.store.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl Iterator<Item = (&K, Entity)> + '_ {
+        self.store
+            .range(range)
+            .flat_map(|(key, entities)| entities.iter().map(move |&entity| (key, entity)))
+    }
+}
+
+impl<C: Component, K: Eq + Clone + Send + Sync + 'static, S: IndexStore<K>> AnyIndex for Index<C, K, S> {
+    fn component(&self) -> ComponentId {
+        ComponentId::of::<C>()
+    }
+
+    fn insert(&mut self, access: StorageAccess<'_>, entity: Entity) {
+        if let Some(component) = access.component::<C>(entity) {
+            self.set(entity, (self.key)(component));
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(key) = self.keys.remove(&entity) {
+            self.store.remove(&key, entity);
+        }
+    }
+
+    fn refresh(&mut self, access: StorageAccess<'_>) {
+        let tick = change_tick();
+        let id = ComponentId::of::<C>();
+        let storage = match access.components().get::<C>() {
+            | Some(storage) => storage,
+            | None => return,
+        };
+
+        for archetype in access.archetypes().iter().filter(|a| a.layout.components.contains(&id)) {
+            let (components, ticks) = match (storage.get(archetype.index), storage.change_ticks(archetype.index)) {
+                | (Some(components), Some(ticks)) if ticks.column() > self.tick => (components, ticks),
+                | _ => continue,
+            };
+
+            for (row, &entity) in archetype.entities.iter().enumerate() {
+                let component = ComponentIndex(row as u32);
+
+                if ticks.row(component).is_none_or(|t| t > self.tick) {
+                    self.set(entity, (self.key)(components.get(component).unwrap()));
+                }
+            }
+        }
+
+        self.tick = tick;
+    }
+
+    fn is_stale(&self, access: StorageAccess<'_>) -> bool {
+        let id = ComponentId::of::<C>();
+        let storage = match access.components().get::<C>() {
+            | Some(storage) => storage,
+            | None => return false,
+        };
+
+        access
+            .archetypes()
+            .iter()
+            .filter(|a| a.layout.components.contains(&id))
+            .any(|a| storage.change_ticks(a.index).is_some_and(|ticks| ticks.column() > self.tick))
+    }
+
+    fn invalidate(&mut self) {
+        self.keys.clear();
+        self.store.clear();
+        self.tick = 0;
+    }
+}
+
+impl dyn AnyIndex {
+    pub fn is<I: AnyIndex>(&self) -> bool {
+        self.type_id() == TypeId::of::<I>()
+    }
+
+    pub fn downcast_ref<I: AnyIndex>(&self) -> Option<&I> {
+        if self.is::<I>() {
+            Some(unsafe { &*(self as *const _ as *const I) })
+        } else {
+            None
+        }
+    }
+}
+
+impl Indexes {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
+    pub(crate) fn notify(&mut self, kind: HookKind, entity: Entity, components: &[ComponentId], access: StorageAccess<'_>) {
+        for index in self.indexes.values_mut() {
+            let index = index.get_mut().unwrap();
+
+            if !components.contains(&index.component()) {
+                continue;
+            }
+
+            match kind {
+                | HookKind::Add => {},
+                | HookKind::Insert => index.insert(access, entity),
+                | HookKind::Remove => index.remove(entity),
+            }
+        }
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        for index in self.indexes.values_mut() {
+            index.get_mut().unwrap().invalidate();
+        }
+    }
+}
+
+impl<'a, I: AnyIndex> Deref for IndexRef<'a, I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        self.guard.downcast_ref().unwrap()
+    }
+}
+
+impl World {
+    pub fn add_index<I: AnyIndex>(&mut self, mut index: I) {
+        index.refresh(self.storage_access());
+        self.indexes
+            .indexes
+            .insert(TypeId::of::<I>(), RwLock::new(Box::new(index)));
+    }
+
+    pub fn remove_index<I: AnyIndex>(&mut self) -> bool {
+        self.indexes.indexes.remove(&TypeId::of::<I>()).is_some()
+    }
+
+    pub fn has_index<I: AnyIndex>(&self) -> bool {
+        self.indexes.indexes.contains_key(&TypeId::of::<I>())
+    }
+
+    pub fn index<I: AnyIndex>(&self) -> IndexRef<'_, I> {
+        self.try_index().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_index<I: AnyIndex>(&self) -> Result<IndexRef<'_, I>, EcsError> {
+        let name = type_name::<I>();
+        let index = self
+            .indexes
+            .indexes
+            .get(&TypeId::of::<I>())
+            .ok_or(EcsError::MissingIndex { index: name })?;
+
+        match index.try_write() {
+            | Ok(mut index) => index.refresh(self.storage_access()),
+            | Err(TryLockError::WouldBlock) => {},
+            | Err(TryLockError::Poisoned(err)) => panic!("{}", err),
+        }
+
+        let guard = index.read().unwrap();
+
+        if guard.is_stale(self.storage_access()) {
+            return Err(EcsError::StaleIndex { index: name });
+        }
+
+        Ok(IndexRef {
+            guard,
+            _marker: PhantomData,
+        })
+    }
+}
@@ -1,8 +1,9 @@
 use crate::archetype::{Archetype, ArchetypeIndex};
-use crate::component::{Component, ComponentIndex};
+use crate::component::{Component, ComponentId, ComponentIndex};
 use crate::entity::Entity;
+use crate::error::EcsError;
 use crate::modify::{EditAnyComponent, EditComponent, EditComponents};
-use std::any::TypeId;
+use crate::storage::AnyArchetypeStorage;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 pub struct EntityInserter<'a> {
@@ -42,17 +43,25 @@ impl<'a> EntityInserter<'a> {
     }
 
     pub fn component<T: Component>(&mut self) -> ComponentInserter<'a, T> {
-        ComponentInserter {
-            edit: self.edit.get::<T>().unwrap(),
+        self.try_component().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn any_component(&mut self, ty: ComponentId) -> AnyComponentInserter<'a> {
+        self.try_any_component(ty).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_component<T: Component>(&mut self) -> Result<ComponentInserter<'a, T>, EcsError> {
+        Ok(ComponentInserter {
+            edit: self.edit.try_get::<T>()?,
             archetype: self.archetype.index,
-        }
+        })
     }
 
-    pub fn any_component<T: Component>(&mut self, ty: TypeId) -> AnyComponentInserter<'a> {
-        AnyComponentInserter {
-            edit: self.edit.get_any(ty).unwrap(),
+    pub fn try_any_component(&mut self, ty: ComponentId) -> Result<AnyComponentInserter<'a>, EcsError> {
+        Ok(AnyComponentInserter {
+            edit: self.edit.try_get_any(ty)?,
             archetype: self.archetype.index,
-        }
+        })
     }
 
     pub fn finish_entity(&mut self) {
@@ -76,6 +85,14 @@ impl<'a, T: Component> ComponentInserter<'a, T> {
 }
 
 impl<'a> AnyComponentInserter<'a> {
+    pub(crate) fn storage_mut(&mut self) -> (&mut dyn AnyArchetypeStorage, ArchetypeIndex) {
+        (&mut *self.edit, self.archetype)
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` valid, initialized values of this column's component type. The values are moved into
+    /// the column and must not be used or dropped by the caller afterwards.
     pub unsafe fn extend_memcpy(&mut self, ptr: *const u8, len: usize) {
         self.edit.extend_memcpy(self.archetype, ptr, len);
     }
@@ -1,14 +1,25 @@
 pub mod archetype;
+pub mod checksum;
 pub mod component;
 pub mod entity;
+pub mod error;
 pub mod filter;
+pub mod hook;
+pub mod index;
 pub mod insert;
 pub mod modify;
+pub mod prefab;
 pub mod query;
+pub mod reflect;
+pub mod registry;
+pub mod relation;
+pub mod replication;
 pub mod resource;
 pub mod schedule;
+pub mod snapshot;
 pub mod storage;
 pub mod subworld;
 pub mod system;
+pub mod transfer;
 pub mod type_list;
 pub mod world;
@@ -1,23 +1,24 @@
-use crate::component::Component;
+use crate::component::{Component, ComponentId};
+use crate::error::EcsError;
 use crate::storage::{ArchetypeStorage, AnyArchetypeStorage, Components};
-use std::any::TypeId;
+use std::any::type_name;
 use std::collections::HashSet;
 use std::ops::{Deref, DerefMut};
 
 pub struct EditComponents<'a> {
     components: &'a mut Components,
-    borrowed: HashSet<TypeId>,
+    borrowed: HashSet<ComponentId>,
 }
 
 pub struct EditComponent<'a, T: Component> {
-    borrowed: *mut HashSet<TypeId>,
+    borrowed: *mut HashSet<ComponentId>,
     storage: &'a mut ArchetypeStorage<T>,
 }
 
 pub struct EditAnyComponent<'a> {
-    borrowed: *mut HashSet<TypeId>,
+    borrowed: *mut HashSet<ComponentId>,
     storage: &'a mut dyn AnyArchetypeStorage,
-    ty: TypeId,
+    ty: ComponentId,
 }
 
 impl Components {
@@ -31,20 +32,55 @@ impl Components {
 
 impl<'a> EditComponents<'a> {
     pub fn get<T: Component>(&mut self) -> Option<EditComponent<'a, T>> {
-        assert!(self.borrowed.insert(TypeId::of::<T>()));
+        match self.try_get() {
+            | Ok(edit) => Some(edit),
+            | Err(EcsError::UnregisteredComponent { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn get_any(&mut self, ty: ComponentId) -> Option<EditAnyComponent<'a>> {
+        match self.try_get_any(ty) {
+            | Ok(edit) => Some(edit),
+            | Err(EcsError::UnregisteredComponent { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
+    }
 
-        Some(EditComponent {
+    pub fn try_get<T: Component>(&mut self) -> Result<EditComponent<'a, T>, EcsError> {
+        let ty = ComponentId::of::<T>();
+        let storage = self
+            .components
+            .get_mut::<T>()
+            .ok_or(EcsError::UnregisteredComponent { component: ty })?;
+
+        if !self.borrowed.insert(ty) {
+            return Err(EcsError::ComponentBorrowed {
+                component: type_name::<T>().to_owned(),
+            });
+        }
+
+        Ok(EditComponent {
             borrowed: &mut self.borrowed,
-            storage: Self::extend_lifetime(self.components.get_mut::<T>()?),
+            storage: Self::extend_lifetime(storage),
         })
     }
 
-    pub fn get_any(&mut self, ty: TypeId) -> Option<EditAnyComponent<'a>> {
-        assert!(self.borrowed.insert(ty));
+    pub fn try_get_any(&mut self, ty: ComponentId) -> Result<EditAnyComponent<'a>, EcsError> {
+        let storage = self
+            .components
+            .get_any_mut(ty)
+            .ok_or(EcsError::UnregisteredComponent { component: ty })?;
+
+        if !self.borrowed.insert(ty) {
+            return Err(EcsError::ComponentBorrowed {
+                component: storage.component_name().to_owned(),
+            });
+        }
 
-        Some(EditAnyComponent {
+        Ok(EditAnyComponent {
             borrowed: &mut self.borrowed,
-            storage: Self::extend_lifetime(self.components.get_any_mut(ty)?),
+            storage: Self::extend_lifetime(storage),
             ty,
         })
     }
@@ -86,7 +122,7 @@ impl<'a, T: Component> Drop for EditComponent<'a, T> {
     fn drop(&mut self) {
         unsafe {
             let borrowed = &mut *self.borrowed;
-            borrowed.remove(&TypeId::of::<T>());
+            borrowed.remove(&ComponentId::of::<T>());
         }
     }
 }
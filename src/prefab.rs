@@ -0,0 +1,326 @@
+use crate::archetype::{ArchetypeIndex, ArchetypeLayout};
+use crate::component::{Component, ComponentId, ComponentIndex, ComponentSource};
+use crate::entity::Entity;
+use crate::error::EcsError;
+use crate::relation::{RelationEdge, Relations};
+use crate::storage::{AnyArchetypeStorage, ArchetypeStorage, BlobArchetypeStorage, Storage};
+use crate::subworld::AnyWorld;
+use crate::world::World;
+use std::any::{type_name, Any};
+
+type LinkFn = Box<dyn Fn(&mut World, Entity, Entity)>;
+type CaptureFn = fn(&dyn AnyArchetypeStorage, ArchetypeIndex, ComponentIndex) -> Option<Box<dyn Any>>;
+
+#[derive(Default)]
+pub struct Prefab {
+    components: Vec<PrefabComponent>,
+    children: Vec<PrefabChild>,
+    relations: Vec<(RelationEdge, Entity)>,
+}
+
+struct PrefabComponent {
+    id: ComponentId,
+    value: Box<dyn Any>,
+    fns: CloneFns,
+}
+
+struct PrefabChild {
+    prefab: Prefab,
+    link: LinkFn,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct CloneFns {
+    ctor: fn() -> Box<dyn AnyArchetypeStorage>,
+    capture: CaptureFn,
+    insert: fn(&mut dyn AnyArchetypeStorage, ArchetypeIndex, &dyn Any, usize),
+    pub(crate) column: fn(&dyn AnyArchetypeStorage, ArchetypeIndex) -> Option<Box<dyn Any + Send + Sync>>,
+    pub(crate) restore: fn(&mut dyn AnyArchetypeStorage, ArchetypeIndex, &dyn Any),
+}
+
+impl CloneFns {
+    pub(crate) fn of<T: Component + Clone>() -> Self {
+        Self {
+            ctor: ArchetypeStorage::<T>::any,
+            capture: |storage, archetype, component| {
+                storage
+                    .downcast_ref::<T>()?
+                    .get(archetype)?
+                    .get(component)
+                    .map(|value| Box::new(value.clone()) as Box<dyn Any>)
+            },
+            insert: |storage, archetype, value, count| {
+                let value = value.downcast_ref::<T>().unwrap();
+
+                storage
+                    .downcast_mut::<T>()
+                    .unwrap()
+                    .extend(archetype, (0..count).map(|_| value.clone()));
+            },
+            column: |storage, archetype| {
+                let column = storage.downcast_ref::<T>()?.get(archetype)?;
+
+                Some(Box::new(column.iter().cloned().collect::<Vec<T>>()))
+            },
+            restore: |storage, archetype, column| {
+                let column = column.downcast_ref::<Vec<T>>().unwrap();
+
+                storage.clear(archetype);
+                storage
+                    .downcast_mut::<T>()
+                    .unwrap()
+                    .extend(archetype, column.iter().cloned());
+            },
+        }
+    }
+
+    pub(crate) fn blob() -> Self {
+        Self {
+            ctor: BlobArchetypeStorage::unregistered,
+            capture: |storage, archetype, component| {
+                storage
+                    .as_blob()?
+                    .get(archetype)?
+                    .bytes(component)
+                    .map(|bytes| Box::new(bytes.to_vec()) as Box<dyn Any>)
+            },
+            insert: |storage, archetype, value, count| {
+                let bytes = value.downcast_ref::<Vec<u8>>().unwrap();
+
+                for _ in 0..count {
+                    unsafe { storage.extend_memcpy(archetype, bytes.as_ptr(), 1) };
+                }
+            },
+            column: |storage, archetype| {
+                let column = storage.as_blob()?.get(archetype)?;
+                let bytes = (0..column.len())
+                    .flat_map(|i| column.bytes(ComponentIndex(i as u32)).unwrap().iter().copied())
+                    .collect::<Vec<u8>>();
+
+                Some(Box::new((column.len(), bytes)))
+            },
+            restore: |storage, archetype, column| {
+                let (len, bytes) = column.downcast_ref::<(usize, Vec<u8>)>().unwrap();
+                let size = storage.as_blob().unwrap().descriptor().layout().size();
+
+                storage.clear(archetype);
+
+                for i in 0..*len {
+                    unsafe { storage.extend_memcpy(archetype, bytes.as_ptr().add(i * size), 1) };
+                }
+            },
+        }
+    }
+}
+
+impl Prefab {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: Component + Clone>(mut self, component: T) -> Self {
+        let id = ComponentId::of::<T>();
+
+        self.components.retain(|c| c.id != id);
+        self.components.push(PrefabComponent {
+            id,
+            value: Box::new(component),
+            fns: CloneFns::of::<T>(),
+        });
+
+        self
+    }
+
+    pub fn with_child<R: Component + Clone>(mut self, relation: R, child: Prefab) -> Self {
+        self.children.push(PrefabChild {
+            prefab: child,
+            link: Box::new(move |world, child, parent| {
+                world.add_relation(child, relation.clone(), parent);
+            }),
+        });
+
+        self
+    }
+
+    pub fn contains(&self, id: ComponentId) -> bool {
+        self.components.iter().any(|c| c.id == id)
+    }
+
+    pub fn components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.iter().map(|c| c.id)
+    }
+
+    pub fn get<T: Component>(&self) -> Option<&T> {
+        self.components
+            .iter()
+            .find(|c| c.id == ComponentId::of::<T>())
+            .and_then(|c| c.value.downcast_ref())
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = &Prefab> {
+        self.children.iter().map(|c| &c.prefab)
+    }
+
+    fn capture(world: &World, entity: Entity, skip: Option<ComponentId>) -> Result<Self, EcsError> {
+        let access = world.storage_access();
+        let data = access.entities().get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let layout = &access.archetypes()[data.archetype().0 as usize].layout;
+        let mut prefab = Prefab::new();
+
+        for &id in layout.components.iter().filter(|&&id| Some(id) != skip) {
+            let storage = access.components().get_any(id).unwrap();
+            let not_cloneable = || EcsError::NotCloneable {
+                component: storage.component_name().to_owned(),
+            };
+            let fns = world
+                .registry()
+                .get(id)
+                .and_then(|info| info.clone_fns())
+                .ok_or_else(not_cloneable)?;
+            let value = (fns.capture)(storage, data.archetype(), data.component()).ok_or_else(not_cloneable)?;
+
+            prefab.components.push(PrefabComponent { id, value, fns });
+        }
+
+        prefab.relations = world.outgoing_relations(entity);
+        prefab.relations.retain(|(edge, _)| Some(edge.component) != skip);
+        Ok(prefab)
+    }
+
+    fn layout(&self, mut layout: ArchetypeLayout) -> ArchetypeLayout {
+        for c in &self.components {
+            if !layout.components.contains(&c.id) {
+                layout.add_any(c.id, c.fns.ctor);
+            }
+        }
+
+        layout
+    }
+}
+
+impl World {
+    pub fn clone_entity(&mut self, entity: Entity) -> Result<Entity, EcsError> {
+        let prefab = self.prefab(entity)?;
+
+        self.instantiate(&prefab)
+    }
+
+    pub fn prefab(&self, entity: Entity) -> Result<Prefab, EcsError> {
+        Prefab::capture(self, entity, None)
+    }
+
+    pub fn prefab_with_children<R: Component + Clone>(&self, entity: Entity) -> Result<Prefab, EcsError> {
+        self.capture_tree::<R>(entity, None, &mut Vec::new())
+    }
+
+    pub fn instantiate(&mut self, prefab: &Prefab) -> Result<Entity, EcsError> {
+        self.instantiate_with(prefab, ())
+    }
+
+    pub fn instantiate_with<T: ComponentSource>(&mut self, prefab: &Prefab, overrides: T) -> Result<Entity, EcsError> {
+        self.instantiate_batch(prefab, std::iter::once(overrides))
+            .map(|spawned| spawned[0])
+    }
+
+    pub fn instantiate_batch<T, I>(&mut self, prefab: &Prefab, overrides: I) -> Result<Vec<Entity>, EcsError>
+    where
+        T: ComponentSource,
+        I: IntoIterator<Item = T>,
+    {
+        let mut groups: Vec<(ArchetypeLayout, Vec<(usize, T)>)> = Vec::new();
+        let mut count = 0;
+
+        for (i, source) in overrides.into_iter().enumerate() {
+            let layout = source.layout()?;
+
+            match groups.iter_mut().find(|(l, _)| *l == layout) {
+                | Some((_, sources)) => sources.push((i, source)),
+                | None => groups.push((layout, vec![(i, source)])),
+            }
+
+            count += 1;
+        }
+
+        let mut spawned = vec![Entity(0); count];
+
+        for (layout, sources) in groups {
+            let template = prefab
+                .components
+                .iter()
+                .filter(|c| !layout.components.contains(&c.id))
+                .collect::<Vec<_>>();
+            let relations = prefab
+                .relations
+                .iter()
+                .filter(|(edge, _)| !layout.components.contains(&edge.component))
+                .cloned()
+                .collect::<Vec<_>>();
+            let (slots, sources): (Vec<_>, Vec<_>) = sources.into_iter().unzip();
+            let layout = prefab.layout(layout);
+            let entities = self.spawn_batch(None, layout, |inserter| {
+                for c in &template {
+                    let mut column = inserter.any_component(c.id);
+                    let (storage, archetype) = column.storage_mut();
+
+                    (c.fns.insert)(storage, archetype, &*c.value, slots.len());
+                }
+
+                for source in sources {
+                    source.insert_components(inserter);
+                }
+            })?;
+
+            for (slot, entity) in slots.into_iter().zip(entities) {
+                self.relink(entity, &relations);
+                spawned[slot] = entity;
+            }
+        }
+
+        self.instantiate_children(prefab, &spawned)?;
+        Ok(spawned)
+    }
+
+    fn instantiate_children(&mut self, prefab: &Prefab, parents: &[Entity]) -> Result<(), EcsError> {
+        for child in &prefab.children {
+            let children = self.instantiate_batch(&child.prefab, vec![(); parents.len()])?;
+
+            for (&parent, entity) in parents.iter().zip(children) {
+                (child.link)(self, entity, parent);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn capture_tree<R: Component + Clone>(
+        &self,
+        entity: Entity,
+        skip: Option<ComponentId>,
+        ancestors: &mut Vec<Entity>,
+    ) -> Result<Prefab, EcsError> {
+        if ancestors.contains(&entity) {
+            return Err(EcsError::RelationCycle {
+                entity,
+                relation: type_name::<R>(),
+            });
+        }
+
+        let mut prefab = Prefab::capture(self, entity, skip)?;
+
+        ancestors.push(entity);
+
+        for child in self.sources::<R>(entity) {
+            let relation = self
+                .relations::<R>(child)
+                .and_then(|r| r.get(entity))
+                .cloned()
+                .unwrap();
+            let captured = self.capture_tree::<R>(child, Some(ComponentId::of::<Relations<R>>()), ancestors)?;
+
+            prefab = prefab.with_child(relation, captured);
+        }
+
+        ancestors.pop();
+        Ok(prefab)
+    }
+}
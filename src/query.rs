@@ -1,24 +1,47 @@
+mod dynamic;
 mod entity;
+mod entity_ref;
+mod cursor;
+mod has;
 mod multiple;
 mod read;
+mod relation;
+mod sorted;
 mod write;
 mod try_read;
 mod try_write;
 
 pub use crate::resource::{Read, Readonly, Write, TryRead, TryWrite};
+pub use cursor::QueryCursor;
+pub use dynamic::{DynamicAccess, DynamicItem, DynamicIter, DynamicQuery, DynamicRow};
+pub use entity_ref::EntityRef;
+pub use has::Has;
 pub use multiple::Multiple;
+pub use relation::{Related, Target};
+pub use sorted::SortedQuery;
 
 use crate::{
-    archetype::{Archetype, ArchetypeIndex},
-    component::Component,
+    archetype::ArchetypeIndex,
+    component::{Component, ComponentId},
     entity::Entity,
+    error::EcsError,
     filter::LayoutFilter,
-    storage::{ArchetypeStorage, Components, Storage},
+    storage::Storage,
     subworld::AnyWorld,
     world::StorageAccess,
 };
 
-use std::{sync::Once, marker::PhantomData};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    convert::TryInto,
+    marker::PhantomData,
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
 
 pub trait IntoQuery: Sized {
     type Fetch: for<'world> Fetch<'world>;
@@ -29,13 +52,37 @@ pub trait IntoQuery: Sized {
 }
 
 pub struct Query<T: for<'world> Fetch<'world>> {
-    init: Once,
-    archetypes: Vec<ArchetypeIndex>,
+    cache: Mutex<ArchetypeCache>,
+    access: QueryAccess,
     _marker: PhantomData<T>,
 }
 
+#[derive(Default, Clone)]
+struct ArchetypeCache {
+    scanned: usize,
+    archetypes: Arc<Vec<ArchetypeIndex>>,
+}
+
+pub(crate) struct ArchetypeList {
+    archetypes: Arc<Vec<ArchetypeIndex>>,
+    front: usize,
+    back: usize,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct QueryAccess {
+    reads: Vec<ComponentId>,
+    writes: Vec<ComponentId>,
+    reads_all: bool,
+    conflict: Option<ComponentId>,
+    names: HashMap<ComponentId, &'static str>,
+}
+
 pub struct QueryIter<'world, 'index, F: Fetch<'world>> {
-    iter: F::Iter,
+    access: StorageAccess<'world>,
+    archetypes: ArchetypeList,
+    front: Option<F::Iter>,
+    back: Option<F::Iter>,
     _marker: PhantomData<&'index [ArchetypeIndex]>,
 }
 
@@ -43,55 +90,150 @@ pub trait Fetch<'world>: FetchFilter {
     type Item: 'world;
     type Iter: Iterator<Item = Self::Item> + 'world;
 
-    fn fetch(
-        components: &'world Components,
-        archetypes: &'world [Archetype],
-        index: &'world [ArchetypeIndex],
-    ) -> Self::Iter;
+    fn fetch(access: StorageAccess<'world>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter;
+}
+
+pub trait ChunkFetch<'world>: FetchFilter {
+    type Chunk: 'world;
+
+    fn fetch_chunk(access: StorageAccess<'world>, archetype: ArchetypeIndex) -> Self::Chunk;
+}
+
+pub struct ChunkIter<'world, 'index, F: ChunkFetch<'world>> {
+    access: StorageAccess<'world>,
+    archetypes: ArchetypeList,
+    _marker: PhantomData<(&'index [ArchetypeIndex], F)>,
 }
 
 pub trait FetchFilter {
     type Layout: LayoutFilter + Default;
+
+    fn access(access: &mut QueryAccess);
 }
 
 impl<T: for<'world> Fetch<'world>> Default for Query<T> {
     fn default() -> Self {
+        Self::try_new().unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl<T: for<'world> Fetch<'world>> Clone for Query<T> {
+    fn clone(&self) -> Self {
         Self {
-            init: Once::new(),
-            archetypes: Vec::new(),
+            cache: Mutex::new(self.cache.lock().unwrap().clone()),
+            access: self.access.clone(),
             _marker: PhantomData,
         }
     }
 }
 
-impl<T: for<'world> Fetch<'world>> Clone for Query<T> {
-    fn clone(&self) -> Self {
-        let init = Once::new();
+impl QueryAccess {
+    pub fn read<T: Component>(&mut self) {
+        let id = ComponentId::of::<T>();
+
+        self.names.insert(id, type_name::<T>());
 
-        if self.init.is_completed() {
-            init.call_once(|| {});
+        if self.writes.contains(&id) {
+            self.conflict.get_or_insert(id);
         }
 
-        Self {
-            init,
-            archetypes: self.archetypes.clone(),
-            _marker: PhantomData,
+        self.reads.push(id);
+    }
+
+    pub fn write<T: Component>(&mut self) {
+        let id = ComponentId::of::<T>();
+
+        self.names.insert(id, type_name::<T>());
+
+        if self.reads_all || self.reads.contains(&id) || self.writes.contains(&id) {
+            self.conflict.get_or_insert(id);
+        }
+
+        self.writes.push(id);
+    }
+
+    pub fn read_all(&mut self) {
+        if let Some(&id) = self.writes.first() {
+            self.conflict.get_or_insert(id);
+        }
+
+        self.reads_all = true;
+    }
+
+    pub fn extend(&mut self, other: &QueryAccess) {
+        if let Some(id) = other.conflict.or_else(|| self.conflicting(other)) {
+            self.conflict.get_or_insert(id);
         }
+
+        self.reads.extend_from_slice(&other.reads);
+        self.writes.extend_from_slice(&other.writes);
+        self.reads_all |= other.reads_all;
+        self.names.extend(&other.names);
+    }
+
+    pub fn check(&self) -> Result<(), EcsError> {
+        match self.conflict {
+            | Some(id) => Err(EcsError::ConflictingAccess {
+                component: self.names[&id].to_owned(),
+            }),
+            | None => Ok(()),
+        }
+    }
+
+    pub fn reads(&self) -> &[ComponentId] {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &[ComponentId] {
+        &self.writes
+    }
+
+    pub fn reads_all(&self) -> bool {
+        self.reads_all
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    pub fn conflicts(&self, other: &QueryAccess) -> bool {
+        self.conflicting(other).is_some()
+    }
+
+    fn conflicting(&self, other: &QueryAccess) -> Option<ComponentId> {
+        let reads = |access: &QueryAccess, id: &ComponentId| access.reads_all || access.reads.contains(id);
+
+        self.writes
+            .iter()
+            .find(|id| reads(other, id) || other.writes.contains(id))
+            .or_else(|| other.writes.iter().find(|id| reads(self, id)))
+            .copied()
     }
 }
 
 impl<T: for<'world> Fetch<'world>> Query<T> {
+    pub fn try_new() -> Result<Self, EcsError> {
+        let mut access = QueryAccess::default();
+
+        T::access(&mut access);
+        access.check()?;
+
+        Ok(Self {
+            cache: Mutex::default(),
+            access,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn access(&self) -> &QueryAccess {
+        &self.access
+    }
+
     pub fn get<'world, W: AnyWorld>(&self, world: &'world W, entity: Entity) -> Option<<T as Fetch<'world>>::Item>
     where
         T: Readonly,
     {
-        let access = world.storage_access();
-        let data = access.entities().get(entity)?;
-        let index: &[ArchetypeIndex] = &[data.archetype()];
-        let index = unsafe { std::mem::transmute(index) };
-        let mut iter = T::fetch(access.components(), access.archetypes(), index);
-
-        iter.nth(data.component().0 as usize)
+        self.try_get(world, entity).ok()
     }
 
     pub fn get_mut<'world, W: AnyWorld>(
@@ -99,62 +241,280 @@ impl<T: for<'world> Fetch<'world>> Query<T> {
         world: &'world mut W,
         entity: Entity,
     ) -> Option<<T as Fetch<'world>>::Item> {
+        self.try_get_mut(world, entity).ok()
+    }
+
+    pub fn try_get<'world, W: AnyWorld>(
+        &self,
+        world: &'world W,
+        entity: Entity,
+    ) -> Result<<T as Fetch<'world>>::Item, EcsError>
+    where
+        T: Readonly,
+    {
+        Self::fetch_one(world.storage_access(), entity)
+    }
+
+    pub fn try_get_mut<'world, W: AnyWorld>(
+        &self,
+        world: &'world mut W,
+        entity: Entity,
+    ) -> Result<<T as Fetch<'world>>::Item, EcsError> {
+        Self::fetch_one(world.storage_access(), entity)
+    }
+
+    pub fn get_many_mut<'world, W: AnyWorld, const N: usize>(
+        &self,
+        world: &'world mut W,
+        entities: [Entity; N],
+    ) -> Option<[<T as Fetch<'world>>::Item; N]> {
+        self.try_get_many_mut(world, entities).ok()
+    }
+
+    pub fn try_get_many_mut<'world, W: AnyWorld, const N: usize>(
+        &self,
+        world: &'world mut W,
+        entities: [Entity; N],
+    ) -> Result<[<T as Fetch<'world>>::Item; N], EcsError> {
+        for (i, entity) in entities.iter().enumerate() {
+            if entities[..i].contains(entity) {
+                return Err(EcsError::DuplicateEntity(*entity));
+            }
+        }
+
         let access = world.storage_access();
-        let data = access.entities().get(entity)?;
-        let index: &[ArchetypeIndex] = &[data.archetype()];
-        let index = unsafe { std::mem::transmute(index) };
-        let mut iter = T::fetch(access.components(), access.archetypes(), index);
+        let items = entities
+            .iter()
+            .map(|&entity| Self::fetch_one(access, entity))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match items.try_into() {
+            | Ok(items) => Ok(items),
+            | Err(_) => unreachable!(),
+        }
+    }
 
-        iter.nth(data.component().0 as usize)
+    pub fn single<'world, W: AnyWorld>(&self, world: &'world W) -> Result<<T as Fetch<'world>>::Item, EcsError>
+    where
+        T: Readonly,
+    {
+        self.fetch_single(world.storage_access())
+    }
+
+    pub fn single_mut<'world, W: AnyWorld>(&self, world: &'world mut W) -> Result<<T as Fetch<'world>>::Item, EcsError> {
+        self.fetch_single(world.storage_access())
+    }
+
+    fn fetch_single<'world>(&self, access: StorageAccess<'world>) -> Result<<T as Fetch<'world>>::Item, EcsError> {
+        let query = type_name::<T>();
+        let mut archetypes = self
+            .find_archetypes(&access)
+            .map(|a| (a, access.archetypes()[a.0 as usize].entities.len()))
+            .filter(|&(_, len)| len > 0);
+
+        match (archetypes.next(), archetypes.map(|(_, len)| len).sum::<usize>()) {
+            | (None, _) => Err(EcsError::NoMatchingEntity { query }),
+            | (Some((archetype, 1)), 0) => Ok(T::fetch(access, archetype, 0..1).next().unwrap()),
+            | (Some((_, len)), rest) => Err(EcsError::MultipleMatchingEntities {
+                query,
+                count: len + rest,
+            }),
+        }
+    }
+
+    fn fetch_one<'world>(access: StorageAccess<'world>, entity: Entity) -> Result<<T as Fetch<'world>>::Item, EcsError> {
+        let data = access.entities().get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let mismatch = EcsError::QueryMismatch {
+            entity,
+            query: type_name::<T>(),
+        };
+
+        if !T::Layout::default().matches(&access.archetypes()[data.archetype().0 as usize].layout.components) {
+            return Err(mismatch);
+        }
+
+        let row = data.component().0 as usize;
+
+        T::fetch(access, data.archetype(), row..row + 1).next().ok_or(mismatch)
     }
 
     pub fn iter<'world, 'index, W: AnyWorld>(&'index self, world: &'world W) -> QueryIter<'world, 'index, T>
     where
         T: Readonly,
     {
+        self.query_iter(world.storage_access())
+    }
+
+    pub fn iter_mut<'world, 'index, W: AnyWorld>(&'index self, world: &'world mut W) -> QueryIter<'world, 'index, T> {
+        self.query_iter(world.storage_access())
+    }
+
+    pub fn count<W: AnyWorld>(&self, world: &W) -> usize {
         let access = world.storage_access();
-        let index = self.find_archetypes(&access);
-        let index = unsafe { std::mem::transmute::<_, &'world [ArchetypeIndex]>(index) };
 
+        self.find_archetypes(&access)
+            .map(|a| access.archetypes()[a.0 as usize].entities.len())
+            .sum()
+    }
+
+    fn query_iter<'world, 'index>(&'index self, access: StorageAccess<'world>) -> QueryIter<'world, 'index, T> {
         QueryIter {
-            iter: T::fetch(access.components(), access.archetypes(), index),
+            archetypes: self.find_archetypes(&access),
+            access,
+            front: None,
+            back: None,
             _marker: PhantomData,
         }
     }
 
-    pub fn iter_mut<'world, 'index, W: AnyWorld>(&'index self, world: &'world mut W) -> QueryIter<'world, 'index, T> {
-        let access = world.storage_access();
-        let index = self.find_archetypes(&access);
-        let index = unsafe { std::mem::transmute::<_, &'world [ArchetypeIndex]>(index) };
+    pub fn iter_chunks<'world, 'index, W: AnyWorld>(&'index self, world: &'world W) -> ChunkIter<'world, 'index, T>
+    where
+        T: Readonly + ChunkFetch<'world>,
+    {
+        self.chunks(world.storage_access())
+    }
 
-        QueryIter {
-            iter: T::fetch(access.components(), access.archetypes(), index),
+    pub fn iter_chunks_mut<'world, 'index, W: AnyWorld>(&'index self, world: &'world mut W) -> ChunkIter<'world, 'index, T>
+    where
+        T: ChunkFetch<'world>,
+    {
+        self.chunks(world.storage_access())
+    }
+
+    fn chunks<'world, 'index>(&'index self, access: StorageAccess<'world>) -> ChunkIter<'world, 'index, T>
+    where
+        T: ChunkFetch<'world>,
+    {
+        ChunkIter {
+            archetypes: self.find_archetypes(&access),
+            access,
             _marker: PhantomData,
         }
     }
 
-    fn find_archetypes<'world, 'index>(&'index self, access: &StorageAccess<'world>) -> &'index [ArchetypeIndex] {
-        if !self.init.is_completed() {
-            let archetypes = &self.archetypes as *const Vec<_> as *mut Vec<_>;
+    pub fn par_for_each<'world, W: AnyWorld, F>(&self, world: &'world W, batch_size: usize, f: F)
+    where
+        T: Readonly,
+        F: Fn(<T as Fetch<'world>>::Item) + Sync,
+    {
+        self.for_each_batch(world.storage_access(), batch_size, f)
+    }
+
+    pub fn par_for_each_mut<'world, W: AnyWorld, F>(&self, world: &'world mut W, batch_size: usize, f: F)
+    where
+        F: Fn(<T as Fetch<'world>>::Item) + Sync,
+    {
+        self.for_each_batch(world.storage_access(), batch_size, f)
+    }
 
-            self.init.call_once(move || unsafe {
-                let filter = T::Layout::default();
-    
-                *archetypes = access
-                    .archetypes()
-                    .iter()
-                    .filter_map(|a| {
-                        if filter.matches(&a.layout.components) {
-                            Some(a.index)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-            });
+    fn for_each_batch<'world, F>(&self, access: StorageAccess<'world>, batch_size: usize, f: F)
+    where
+        F: Fn(<T as Fetch<'world>>::Item) + Sync,
+    {
+        assert!(batch_size > 0, "batch size must be greater than zero");
+
+        let batches = self
+            .find_archetypes(&access)
+            .flat_map(|archetype| {
+                let len = access.archetypes()[archetype.0 as usize].entities.len();
+
+                (0..len)
+                    .step_by(batch_size)
+                    .map(move |start| (archetype, start..len.min(start + batch_size)))
+            })
+            .collect::<Vec<_>>();
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(batches.len());
+        let next = AtomicUsize::new(0);
+        let run = || {
+            while let Some((archetype, rows)) = batches.get(next.fetch_add(1, Ordering::Relaxed)) {
+                T::fetch(access, *archetype, rows.clone()).for_each(&f);
+            }
+        };
+
+        if threads <= 1 {
+            return run();
         }
 
-        &self.archetypes
+        std::thread::scope(|scope| {
+            for _ in 1..threads {
+                scope.spawn(run);
+            }
+
+            run();
+        });
+    }
+
+    pub(crate) fn find_archetypes(&self, access: &StorageAccess<'_>) -> ArchetypeList {
+        let mut cache = self.cache.lock().unwrap();
+        let archetypes = access.archetypes();
+
+        if cache.scanned > archetypes.len() {
+            *cache = ArchetypeCache::default();
+        }
+
+        if cache.scanned < archetypes.len() {
+            let filter = T::Layout::default();
+            let added = archetypes[cache.scanned..]
+                .iter()
+                .filter(|a| filter.matches(&a.layout.components))
+                .map(|a| a.index);
+
+            Arc::make_mut(&mut cache.archetypes).extend(added);
+            cache.scanned = archetypes.len();
+        }
+
+        ArchetypeList {
+            front: 0,
+            back: cache.archetypes.len(),
+            archetypes: cache.archetypes.clone(),
+        }
+    }
+}
+
+impl ArchetypeList {
+    fn as_slice(&self) -> &[ArchetypeIndex] {
+        &self.archetypes[self.front..self.back]
+    }
+}
+
+impl Iterator for ArchetypeList {
+    type Item = ArchetypeIndex;
+
+    fn next(&mut self) -> Option<ArchetypeIndex> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.archetypes[self.front - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl DoubleEndedIterator for ArchetypeList {
+    fn next_back(&mut self) -> Option<ArchetypeIndex> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.archetypes[self.back])
+    }
+}
+
+impl ExactSizeIterator for ArchetypeList {
+}
+
+impl<'world, 'index, T: Fetch<'world>> QueryIter<'world, 'index, T> {
+    fn rows(&self, archetype: ArchetypeIndex) -> T::Iter {
+        let len = self.access.archetypes()[archetype.0 as usize].entities.len();
+
+        T::fetch(self.access, archetype, 0..len)
     }
 }
 
@@ -162,7 +522,72 @@ impl<'world, 'index, T: Fetch<'world>> Iterator for QueryIter<'world, 'index, T>
     type Item = T::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        loop {
+            if let Some(item) = self.front.as_mut().and_then(Iterator::next) {
+                return Some(item);
+            }
+
+            match self.archetypes.next() {
+                | Some(archetype) => self.front = Some(self.rows(archetype)),
+                | None => return self.back.as_mut()?.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = |iter: &Option<T::Iter>| iter.as_ref().map_or((0, Some(0)), |i| i.size_hint());
+        let (front, back) = (hint(&self.front), hint(&self.back));
+        let rows = self
+            .archetypes
+            .as_slice()
+            .iter()
+            .map(|a| self.access.archetypes()[a.0 as usize].entities.len())
+            .sum::<usize>();
+        let upper = match (front.1, back.1) {
+            | (Some(front), Some(back)) => Some(front + back + rows),
+            | _ => None,
+        };
+
+        (front.0 + back.0 + rows, upper)
+    }
+}
+
+impl<'world, 'index, T: Fetch<'world>> DoubleEndedIterator for QueryIter<'world, 'index, T>
+where
+    T::Iter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.back.as_mut().and_then(DoubleEndedIterator::next_back) {
+                return Some(item);
+            }
+
+            match self.archetypes.next_back() {
+                | Some(archetype) => self.back = Some(self.rows(archetype)),
+                | None => return self.front.as_mut()?.next_back(),
+            }
+        }
+    }
+}
+
+impl<'world, 'index, T: Fetch<'world>> ExactSizeIterator for QueryIter<'world, 'index, T>
+where
+    T::Iter: ExactSizeIterator,
+{
+}
+
+impl<'world, 'index, T: ChunkFetch<'world>> Iterator for ChunkIter<'world, 'index, T> {
+    type Item = (&'world [Entity], T::Chunk);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let archetype = self.archetypes.next()?;
+            let entities = &self.access.archetypes()[archetype.0 as usize].entities;
+
+            if !entities.is_empty() {
+                return Some((entities, T::fetch_chunk(self.access, archetype)));
+            }
+        }
     }
 }
 
@@ -0,0 +1,123 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCursor {
+    archetype: ArchetypeIndex,
+    row: usize,
+    passes: u64,
+}
+
+impl Default for QueryCursor {
+    fn default() -> Self {
+        Self {
+            archetype: ArchetypeIndex(0),
+            row: 0,
+            passes: 0,
+        }
+    }
+}
+
+impl QueryCursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn position(&self) -> (ArchetypeIndex, usize) {
+        (self.archetype, self.row)
+    }
+
+    pub fn passes(&self) -> u64 {
+        self.passes
+    }
+
+    fn advance(
+        &mut self,
+        runs: Vec<(ArchetypeIndex, Range<usize>)>,
+        remaining: &mut usize,
+        batches: &mut Vec<(ArchetypeIndex, Range<usize>)>,
+    ) {
+        for (archetype, rows) in runs {
+            if *remaining == 0 {
+                break;
+            }
+
+            let end = rows.end.min(rows.start + *remaining);
+
+            self.archetype = archetype;
+            self.row = end;
+
+            if end > rows.start {
+                *remaining -= end - rows.start;
+                batches.push((archetype, rows.start..end));
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.archetype = ArchetypeIndex(0);
+        self.row = 0;
+    }
+}
+impl<T: for<'world> Fetch<'world>> Query<T> {
+    pub fn iter_from_cursor<'world, W: AnyWorld>(
+        &self,
+        world: &'world W,
+        cursor: &mut QueryCursor,
+        limit: usize,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'world
+    where
+        T: Readonly,
+    {
+        self.cursor_iter(world.storage_access(), cursor, limit)
+    }
+
+    pub fn iter_from_cursor_mut<'world, W: AnyWorld>(
+        &self,
+        world: &'world mut W,
+        cursor: &mut QueryCursor,
+        limit: usize,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'world {
+        self.cursor_iter(world.storage_access(), cursor, limit)
+    }
+
+    fn cursor_iter<'world>(
+        &self,
+        access: StorageAccess<'world>,
+        cursor: &mut QueryCursor,
+        limit: usize,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'world {
+        let mut batches = Vec::new();
+        let mut remaining = limit;
+
+        if remaining > 0 {
+            let (from, row) = (cursor.archetype, cursor.row);
+            let mut runs = Vec::new();
+            let mut wrapped = Vec::new();
+
+            for archetype in self.find_archetypes(&access) {
+                let len = access.archetypes()[archetype.0 as usize].entities.len();
+
+                if archetype.0 < from.0 {
+                    wrapped.push((archetype, 0..len));
+                } else if archetype.0 > from.0 {
+                    runs.push((archetype, 0..len));
+                } else {
+                    runs.push((archetype, row.min(len)..len));
+                    wrapped.push((archetype, 0..row.min(len)));
+                }
+            }
+
+            cursor.advance(runs, &mut remaining, &mut batches);
+
+            if remaining > 0 {
+                cursor.reset();
+                cursor.passes += 1;
+                cursor.advance(wrapped, &mut remaining, &mut batches);
+            }
+        }
+
+        batches
+            .into_iter()
+            .flat_map(move |(archetype, rows)| T::fetch(access, archetype, rows))
+    }
+}
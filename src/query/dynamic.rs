@@ -0,0 +1,331 @@
+use super::*;
+use crate::component::{ComponentId, ComponentIndex};
+use crate::storage::AnyArchetypeStorage;
+use std::any::Any;
+use std::ptr::NonNull;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicAccess {
+    Read,
+    Write,
+    TryRead,
+    TryWrite,
+    With,
+    Without,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct DynamicQuery {
+    terms: Vec<(ComponentId, DynamicAccess)>,
+}
+
+pub struct DynamicIter<'world> {
+    access: StorageAccess<'world>,
+    fetches: Vec<(ComponentId, bool)>,
+    storages: Vec<Option<&'world dyn AnyArchetypeStorage>>,
+    archetypes: std::vec::IntoIter<ArchetypeIndex>,
+    current: Option<ArchetypeIndex>,
+    row: usize,
+}
+
+pub struct DynamicRow<'world> {
+    entity: Entity,
+    items: Vec<Option<DynamicItem<'world>>>,
+}
+
+pub struct DynamicItem<'world> {
+    id: ComponentId,
+    ptr: NonNull<u8>,
+    any: Option<NonNull<dyn Any>>,
+    size: Option<usize>,
+    mutable: bool,
+    _marker: PhantomData<&'world mut ()>,
+}
+
+impl DynamicAccess {
+    pub fn is_fetch(&self) -> bool {
+        !matches!(self, DynamicAccess::With | DynamicAccess::Without)
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, DynamicAccess::Write | DynamicAccess::TryWrite)
+    }
+}
+
+impl DynamicQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::Read)
+    }
+
+    pub fn write(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::Write)
+    }
+
+    pub fn try_read(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::TryRead)
+    }
+
+    pub fn try_write(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::TryWrite)
+    }
+
+    pub fn with(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::With)
+    }
+
+    pub fn without(self, id: ComponentId) -> Self {
+        self.with_term(id, DynamicAccess::Without)
+    }
+
+    pub fn with_term(mut self, id: ComponentId, access: DynamicAccess) -> Self {
+        assert!(
+            !self.conflicts(id, access),
+            "conflicting access to component {:?} in dynamic query",
+            id
+        );
+
+        self.terms.push((id, access));
+        self
+    }
+
+    pub fn terms(&self) -> &[(ComponentId, DynamicAccess)] {
+        &self.terms
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.terms.iter().all(|(_, access)| !access.is_write())
+    }
+
+    pub fn get<'world, W: AnyWorld>(&self, world: &'world W, entity: Entity) -> Option<DynamicRow<'world>> {
+        assert!(self.is_readonly(), "dynamic query with write access requires `get_mut`");
+        self.fetch_one(world.storage_access(), entity)
+    }
+
+    pub fn get_mut<'world, W: AnyWorld>(&self, world: &'world mut W, entity: Entity) -> Option<DynamicRow<'world>> {
+        self.fetch_one(world.storage_access(), entity)
+    }
+
+    pub fn iter<'world, W: AnyWorld>(&self, world: &'world W) -> DynamicIter<'world> {
+        assert!(self.is_readonly(), "dynamic query with write access requires `iter_mut`");
+        self.fetch(world.storage_access())
+    }
+
+    pub fn iter_mut<'world, W: AnyWorld>(&self, world: &'world mut W) -> DynamicIter<'world> {
+        self.fetch(world.storage_access())
+    }
+
+    fn conflicts(&self, id: ComponentId, access: DynamicAccess) -> bool {
+        access.is_fetch()
+            && self
+                .terms
+                .iter()
+                .any(|&(other, a)| other == id && a.is_fetch() && (a.is_write() || access.is_write()))
+    }
+
+    fn fetch_one<'world>(&self, access: StorageAccess<'world>, entity: Entity) -> Option<DynamicRow<'world>> {
+        let data = access.entities().get(entity)?;
+
+        if !self.matches(&access.archetypes()[data.archetype().0 as usize].layout.components) {
+            return None;
+        }
+
+        let iter = self.fetch_archetypes(access, Vec::new());
+
+        Some(iter.fetch_row(entity, data.archetype(), data.component()))
+    }
+
+    fn fetch<'world>(&self, access: StorageAccess<'world>) -> DynamicIter<'world> {
+        let archetypes = access
+            .archetypes()
+            .iter()
+            .filter(|a| self.matches(&a.layout.components))
+            .map(|a| a.index)
+            .collect();
+
+        self.fetch_archetypes(access, archetypes)
+    }
+
+    fn fetch_archetypes<'world>(&self, access: StorageAccess<'world>, archetypes: Vec<ArchetypeIndex>) -> DynamicIter<'world> {
+        let fetches = self
+            .terms
+            .iter()
+            .filter(|(_, a)| a.is_fetch())
+            .map(|&(id, a)| (id, a.is_write()))
+            .collect::<Vec<_>>();
+
+        DynamicIter {
+            storages: fetches.iter().map(|&(id, _)| access.components().get_any(id)).collect(),
+            archetypes: archetypes.into_iter(),
+            current: None,
+            row: 0,
+            fetches,
+            access,
+        }
+    }
+}
+
+impl LayoutFilter for DynamicQuery {
+    fn matches(&self, components: &[ComponentId]) -> bool {
+        self.terms.iter().all(|&(id, access)| match access {
+            | DynamicAccess::Read | DynamicAccess::Write | DynamicAccess::With => components.contains(&id),
+            | DynamicAccess::Without => !components.contains(&id),
+            | DynamicAccess::TryRead | DynamicAccess::TryWrite => true,
+        })
+    }
+}
+
+impl<'world> DynamicIter<'world> {
+    fn fetch_row(&self, entity: Entity, archetype: ArchetypeIndex, component: ComponentIndex) -> DynamicRow<'world> {
+        let items = self
+            .fetches
+            .iter()
+            .zip(&self.storages)
+            .map(|(&(id, mutable), storage)| {
+                storage.and_then(|s| DynamicItem::new(id, s, archetype, component, mutable))
+            })
+            .collect();
+
+        DynamicRow { entity, items }
+    }
+}
+
+impl<'world> Iterator for DynamicIter<'world> {
+    type Item = DynamicRow<'world>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let archetype = match self.current {
+                | Some(archetype) => archetype,
+                | None => {
+                    self.row = 0;
+                    *self.current.insert(self.archetypes.next()?)
+                },
+            };
+
+            let entities = &self.access.archetypes()[archetype.0 as usize].entities;
+
+            if self.row < entities.len() {
+                let component = ComponentIndex(self.row as u32);
+
+                self.row += 1;
+                return Some(self.fetch_row(entities[component.0 as usize], archetype, component));
+            }
+
+            self.current = None;
+        }
+    }
+}
+
+impl<'world> DynamicRow<'world> {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&DynamicItem<'world>> {
+        self.items.get(index).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DynamicItem<'world>> {
+        self.items.get_mut(index).and_then(Option::as_mut)
+    }
+
+    pub fn items(&self) -> &[Option<DynamicItem<'world>>] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<Option<DynamicItem<'world>>> {
+        self.items
+    }
+}
+
+impl<'world> DynamicItem<'world> {
+    pub(crate) fn new(
+        id: ComponentId,
+        storage: &'world dyn AnyArchetypeStorage,
+        archetype: ArchetypeIndex,
+        component: ComponentIndex,
+        mutable: bool,
+    ) -> Option<Self> {
+        let ptr = storage.get_ptr(archetype, component)?;
+
+        if mutable {
+            storage.mark_changed(archetype, component);
+        }
+
+        Some(Self {
+            id,
+            ptr,
+            any: storage.get_any_ptr(archetype, component),
+            size: storage.as_blob().map(|s| s.descriptor().layout().size()),
+            mutable,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        self.mutable
+    }
+
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> Option<*mut u8> {
+        if self.mutable {
+            Some(self.ptr.as_ptr())
+        } else {
+            None
+        }
+    }
+
+    pub fn as_any(&self) -> Option<&dyn Any> {
+        self.any.map(|any| unsafe { &*any.as_ptr() })
+    }
+
+    pub fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        if self.mutable {
+            self.any.map(|any| unsafe { &mut *any.as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    pub fn downcast_ref<T: Component>(&self) -> Option<&T> {
+        self.as_any().and_then(|any| any.downcast_ref())
+    }
+
+    pub fn downcast_mut<T: Component>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().and_then(|any| any.downcast_mut())
+    }
+
+    pub fn bytes(&self) -> Option<&[u8]> {
+        self.size
+            .map(|size| unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), size) })
+    }
+
+    /// # Safety
+    ///
+    /// See [`BlobStorage::bytes_mut`](crate::storage::BlobStorage::bytes_mut).
+    pub unsafe fn bytes_mut(&mut self) -> Option<&mut [u8]> {
+        match self.size {
+            | Some(size) if self.mutable => Some(unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), size) }),
+            | _ => None,
+        }
+    }
+}
@@ -3,9 +3,7 @@ use crate::entity::Entity;
 use crate::filter::Any;
 
 pub struct EntityIter<'a> {
-    entities: Option<std::slice::Iter<'a, Entity>>,
-    archetypes: &'a [Archetype],
-    index: std::slice::Iter<'a, ArchetypeIndex>,
+    entities: std::slice::Iter<'a, Entity>,
 }
 
 impl IntoQuery for Entity {
@@ -16,38 +14,48 @@ impl<'a> Fetch<'a> for Entity {
     type Item = Entity;
     type Iter = EntityIter<'a>;
 
-    fn fetch(_: &'a Components, archetypes: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
         EntityIter {
-            archetypes,
-            index: index.iter(),
-            entities: None,
+            entities: access.archetypes()[archetype.0 as usize].entities[rows].iter(),
         }
     }
 }
 
+impl<'a> ChunkFetch<'a> for Entity {
+    type Chunk = &'a [Entity];
+
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        &access.archetypes()[archetype.0 as usize].entities
+    }
+}
+
 impl Readonly for Entity {
 }
 
 impl FetchFilter for Entity {
     type Layout = Any;
+
+    fn access(_: &mut QueryAccess) {
+    }
 }
 
 impl<'a> Iterator for EntityIter<'a> {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.entities {
-            | Some(ref mut entities) => match entities.next() {
-                | Some(entity) => Some(*entity),
-                | None => {
-                    self.entities = None;
-                    self.next()
-                },
-            },
-            | None => {
-                self.entities = Some(self.archetypes[self.index.next()?.0 as usize].entities.iter());
-                self.next()
-            },
-        }
+        self.entities.next().copied()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entities.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for EntityIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entities.next_back().copied()
+    }
+}
+
+impl<'a> ExactSizeIterator for EntityIter<'a> {
 }
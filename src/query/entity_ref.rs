@@ -0,0 +1,105 @@
+use super::*;
+use crate::component::ComponentIndex;
+use crate::filter::Any;
+
+#[derive(Clone, Copy)]
+pub struct EntityRef<'a> {
+    access: StorageAccess<'a>,
+    entity: Entity,
+    archetype: ArchetypeIndex,
+    component: ComponentIndex,
+}
+
+pub struct EntityRefIter<'a> {
+    access: StorageAccess<'a>,
+    archetype: ArchetypeIndex,
+    rows: Range<usize>,
+}
+
+impl IntoQuery for EntityRef<'_> {
+    type Fetch = EntityRef<'static>;
+}
+
+impl<'a> Fetch<'a> for EntityRef<'static> {
+    type Item = EntityRef<'a>;
+    type Iter = EntityRefIter<'a>;
+
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        EntityRefIter { access, archetype, rows }
+    }
+}
+
+impl Readonly for EntityRef<'static> {
+}
+
+impl FetchFilter for EntityRef<'static> {
+    type Layout = Any;
+
+    fn access(access: &mut QueryAccess) {
+        access.read_all();
+    }
+}
+
+impl<'a> EntityRef<'a> {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn components(&self) -> &'a [ComponentId] {
+        &self.access.archetypes()[self.archetype.0 as usize].layout.components
+    }
+
+    pub fn contains<T: Component>(&self) -> bool {
+        self.contains_dynamic(ComponentId::of::<T>())
+    }
+
+    pub fn contains_dynamic(&self, id: ComponentId) -> bool {
+        self.components().contains(&id)
+    }
+
+    pub fn get<T: Component>(&self) -> Option<&'a T> {
+        self.access
+            .components()
+            .get::<T>()?
+            .get(self.archetype)?
+            .get(self.component)
+    }
+
+    pub fn get_dynamic(&self, id: ComponentId) -> Option<DynamicItem<'a>> {
+        let storage = self.access.components().get_any(id)?;
+
+        DynamicItem::new(id, storage, self.archetype, self.component, false)
+    }
+}
+
+impl<'a> EntityRefIter<'a> {
+    fn entity_ref(&self, row: usize) -> EntityRef<'a> {
+        EntityRef {
+            access: self.access,
+            entity: self.access.archetypes()[self.archetype.0 as usize].entities[row],
+            archetype: self.archetype,
+            component: ComponentIndex(row as u32),
+        }
+    }
+}
+
+impl<'a> Iterator for EntityRefIter<'a> {
+    type Item = EntityRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| self.entity_ref(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for EntityRefIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|row| self.entity_ref(row))
+    }
+}
+
+impl<'a> ExactSizeIterator for EntityRefIter<'a> {
+}
@@ -0,0 +1,68 @@
+use super::*;
+use crate::filter::Any;
+
+pub struct Has<T>(PhantomData<*const T>);
+
+pub struct HasIter {
+    has: bool,
+    rows: Range<usize>,
+}
+
+impl<T: Component> IntoQuery for Has<T> {
+    type Fetch = Self;
+}
+
+impl<'a, T: Component> Fetch<'a> for Has<T> {
+    type Item = bool;
+    type Iter = HasIter;
+
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        let layout = &access.archetypes()[archetype.0 as usize].layout;
+
+        HasIter {
+            has: layout.components.contains(&ComponentId::of::<T>()),
+            rows,
+        }
+    }
+}
+
+impl<'a, T: Component> ChunkFetch<'a> for Has<T> {
+    type Chunk = bool;
+
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        let layout = &access.archetypes()[archetype.0 as usize].layout;
+
+        layout.components.contains(&ComponentId::of::<T>())
+    }
+}
+
+impl<T> Readonly for Has<T> {
+}
+
+impl<T: Component> FetchFilter for Has<T> {
+    type Layout = Any;
+
+    fn access(_: &mut QueryAccess) {
+    }
+}
+
+impl Iterator for HasIter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|_| self.has)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for HasIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|_| self.has)
+    }
+}
+
+impl ExactSizeIterator for HasIter {
+}
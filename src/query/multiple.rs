@@ -20,16 +20,28 @@ macro_rules! impl_multi {
             type Iter = MultiIter<($($ty::Iter,)+)>;
 
             #[allow(non_snake_case)]
-            fn fetch(components: &'a Components, archetypes: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
-                $(let $ty = $ty::fetch(components, archetypes, index);)*
+            fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+                $(let $ty = $ty::fetch(access, archetype, rows.clone());)*
                 MultiIter(($($ty,)+))
             }
         }
 
+        impl<'a, $($ty: Fetch<'a> + ChunkFetch<'a>),+> ChunkFetch<'a> for Multiple<($($ty,)+)> {
+            type Chunk = ($($ty::Chunk,)+);
+
+            fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+                ($($ty::fetch_chunk(access, archetype),)+)
+            }
+        }
+
         impl<$($ty: Readonly),+> Readonly for Multiple<($($ty,)+)> {}
 
         impl<'a, $($ty: Fetch<'a>),+> FetchFilter for Multiple<($($ty,)+)> {
             type Layout = And<($($ty::Layout,)*)>;
+
+            fn access(access: &mut QueryAccess) {
+                $($ty::access(access);)+
+            }
         }
     };
 }
@@ -56,8 +68,37 @@ macro_rules! impl_multi_iter {
                 $(let $ty = $ty.next()?;)+
                 Some(($($ty,)+))
             }
+
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let Self(($($ty,)+)) = self;
+                let hint = (usize::MAX, None);
+                $(let hint = min_hint(hint, $ty.size_hint());)+
+                hint
+            }
+        }
+
+        impl<$($ty: DoubleEndedIterator),+> DoubleEndedIterator for MultiIter<($($ty,)+)> {
+            #[allow(non_snake_case)]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let Self(($($ty,)+)) = self;
+                $(let $ty = $ty.next_back()?;)+
+                Some(($($ty,)+))
+            }
+        }
+
+        impl<$($ty: ExactSizeIterator),+> ExactSizeIterator for MultiIter<($($ty,)+)> {
         }
     };
 }
 
+fn min_hint(a: (usize, Option<usize>), b: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let upper = match (a.1, b.1) {
+        | (Some(a), Some(b)) => Some(a.min(b)),
+        | (a, b) => a.or(b),
+    };
+
+    (a.0.min(b.0), upper)
+}
+
 impl_multi_iter!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
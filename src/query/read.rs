@@ -2,13 +2,8 @@ use super::*;
 use crate::filter::Component as ComponentFilter;
 use crate::resource::Read;
 
-pub enum ReadIter<'a, T: Component> {
-    Empty,
-    Iter {
-        storage: &'a ArchetypeStorage<T>,
-        components: Option<<T::Storage as Storage<'a, T>>::Iter>,
-        archetypes: std::slice::Iter<'a, ArchetypeIndex>,
-    },
+pub struct ReadIter<'a, T: Component> {
+    components: Option<<T::Storage as Storage<'a, T>>::Iter>,
 }
 
 impl<T: Component> IntoQuery for &T {
@@ -23,45 +18,55 @@ impl<'a, T: Component> Fetch<'a> for Read<T> {
     type Item = &'a T;
     type Iter = ReadIter<'a, T>;
 
-    fn fetch(components: &'a Components, _: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
-        match components.get::<T>() {
-            | None => ReadIter::Empty,
-            | Some(storage) => ReadIter::Iter {
-                storage,
-                components: None,
-                archetypes: index.iter(),
-            },
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        ReadIter {
+            components: access
+                .components()
+                .get::<T>()
+                .and_then(|s| s.get(archetype))
+                .map(|s| s.iter_range(rows)),
+        }
+    }
+}
+
+impl<'a, T: Component> ChunkFetch<'a> for Read<T> {
+    type Chunk = &'a [T];
+
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        match access.components().get::<T>().and_then(|s| s.get(archetype)) {
+            | Some(components) => components.as_slice(),
+            | None => &[],
         }
     }
 }
 
 impl<T: Component> FetchFilter for Read<T> {
     type Layout = ComponentFilter<T>;
+
+    fn access(access: &mut QueryAccess) {
+        access.read::<T>();
+    }
 }
 
 impl<'a, T: Component> Iterator for ReadIter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            | Self::Empty => None,
-            | Self::Iter {
-                storage,
-                components,
-                archetypes,
-            } => match components {
-                | Some(comps) => match comps.next() {
-                    | Some(comp) => Some(comp),
-                    | None => {
-                        *components = None;
-                        self.next()
-                    },
-                },
-                | None => {
-                    *components = storage.get(*archetypes.next()?).map(|s| s.iter());
-                    self.next()
-                },
-            },
-        }
+        self.components.as_mut()?.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.components.as_ref().map_or(0, |c| c.len());
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Component> DoubleEndedIterator for ReadIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.components.as_mut()?.next_back()
+    }
+}
+
+impl<'a, T: Component> ExactSizeIterator for ReadIter<'a, T> {
 }
@@ -0,0 +1,104 @@
+use super::read::ReadIter;
+use super::*;
+use crate::filter::Component as ComponentFilter;
+use crate::relation::Relations;
+
+pub struct Related<R>(PhantomData<*const R>);
+
+/// Fetches `T` from the first target of each entity's `Relations<R>`, or `None` if that target lacks `T`.
+///
+/// Only the first target is resolved; further targets of a multi-target relation are not visited. Use
+/// [`Related`] to iterate all of them.
+pub struct Target<R, T>(PhantomData<(*const R, *const T)>);
+
+pub struct TargetIter<'a, R: Component, T: Component> {
+    relations: ReadIter<'a, Relations<R>>,
+    access: StorageAccess<'a>,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<R: Component> IntoQuery for Related<R> {
+    type Fetch = Self;
+}
+
+impl<'a, R: Component> Fetch<'a> for Related<R> {
+    type Item = &'a Relations<R>;
+    type Iter = ReadIter<'a, Relations<R>>;
+
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        Read::<Relations<R>>::fetch(access, archetype, rows)
+    }
+}
+
+impl<'a, R: Component> ChunkFetch<'a> for Related<R> {
+    type Chunk = &'a [Relations<R>];
+
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        Read::<Relations<R>>::fetch_chunk(access, archetype)
+    }
+}
+
+impl<R> Readonly for Related<R> {
+}
+
+impl<R: Component> FetchFilter for Related<R> {
+    type Layout = ComponentFilter<Relations<R>>;
+
+    fn access(access: &mut QueryAccess) {
+        access.read::<Relations<R>>();
+    }
+}
+
+impl<R: Component, T: Component> IntoQuery for Target<R, T> {
+    type Fetch = Self;
+}
+
+impl<'a, R: Component, T: Component> Fetch<'a> for Target<R, T> {
+    type Item = Option<&'a T>;
+    type Iter = TargetIter<'a, R, T>;
+
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        TargetIter {
+            relations: Read::<Relations<R>>::fetch(access, archetype, rows),
+            access,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> Readonly for Target<R, T> {
+}
+
+impl<R: Component, T: Component> FetchFilter for Target<R, T> {
+    type Layout = ComponentFilter<Relations<R>>;
+
+    fn access(access: &mut QueryAccess) {
+        access.read::<Relations<R>>();
+        access.read::<T>();
+    }
+}
+
+impl<'a, R: Component, T: Component> Iterator for TargetIter<'a, R, T> {
+    type Item = Option<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let relations = self.relations.next()?;
+
+        Some(relations.first().and_then(|target| self.access.component(target)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.relations.size_hint()
+    }
+}
+
+impl<'a, R: Component, T: Component> DoubleEndedIterator for TargetIter<'a, R, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let relations = self.relations.next_back()?;
+
+        Some(relations.first().and_then(|target| self.access.component(target)))
+    }
+}
+
+impl<'a, R: Component, T: Component> ExactSizeIterator for TargetIter<'a, R, T> {
+}
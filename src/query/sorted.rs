@@ -0,0 +1,181 @@
+use super::*;
+use crate::component::ComponentIndex;
+use crate::storage::{change_tick, AnyArchetypeStorage};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::HashMap;
+
+pub struct SortedQuery<T: for<'world> Fetch<'world>, C: Component, K> {
+    query: Query<T>,
+    key: fn(&C) -> K,
+    order: Vec<(K, Entity)>,
+    keys: HashMap<Entity, (K, u64)>,
+    tick: u64,
+    epoch: u64,
+}
+
+impl<T: for<'world> Fetch<'world>> Query<T> {
+    pub fn iter_sorted_by_key<'world, W: AnyWorld, K: Ord, F>(
+        &self,
+        world: &'world W,
+        f: F,
+    ) -> std::vec::IntoIter<<T as Fetch<'world>>::Item>
+    where
+        T: Readonly,
+        F: FnMut(&<T as Fetch<'world>>::Item) -> K,
+    {
+        let mut items = self.iter(world).collect::<Vec<_>>();
+
+        items.sort_by_cached_key(f);
+        items.into_iter()
+    }
+
+    pub fn iter_sorted_by_key_mut<'world, W: AnyWorld, K: Ord, F>(
+        &self,
+        world: &'world mut W,
+        f: F,
+    ) -> std::vec::IntoIter<<T as Fetch<'world>>::Item>
+    where
+        F: FnMut(&<T as Fetch<'world>>::Item) -> K,
+    {
+        let mut items = self.iter_mut(world).collect::<Vec<_>>();
+
+        items.sort_by_cached_key(f);
+        items.into_iter()
+    }
+
+    pub fn sorted_by_key<C: Component, K: Ord + Clone>(self, key: fn(&C) -> K) -> SortedQuery<T, C, K> {
+        SortedQuery {
+            query: self,
+            key,
+            order: Vec::new(),
+            keys: HashMap::new(),
+            tick: 0,
+            epoch: 0,
+        }
+    }
+}
+
+impl<T: for<'world> Fetch<'world>, C: Component, K: Ord + Clone> SortedQuery<T, C, K> {
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.order.iter().map(|&(_, entity)| entity)
+    }
+
+    pub fn update<W: AnyWorld>(&mut self, world: &W) {
+        self.refresh(world.storage_access())
+    }
+
+    pub fn iter<'a, 'world: 'a, W: AnyWorld>(
+        &'a mut self,
+        world: &'world W,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'a
+    where
+        T: Readonly,
+    {
+        self.sorted(world.storage_access())
+    }
+
+    pub fn iter_mut<'a, 'world: 'a, W: AnyWorld>(
+        &'a mut self,
+        world: &'world mut W,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'a {
+        self.sorted(world.storage_access())
+    }
+
+    fn sorted<'a, 'world: 'a>(
+        &'a mut self,
+        access: StorageAccess<'world>,
+    ) -> impl Iterator<Item = <T as Fetch<'world>>::Item> + 'a {
+        self.refresh(access);
+        self.order
+            .iter()
+            .filter_map(move |&(_, entity)| Query::<T>::fetch_one(access, entity).ok())
+    }
+
+    fn refresh(&mut self, access: StorageAccess<'_>) {
+        let tick = change_tick();
+        let id = ComponentId::of::<C>();
+        let mut removed = Vec::new();
+        let mut inserted = Vec::new();
+
+        self.epoch += 1;
+
+        if let Some(storage) = access.components().get::<C>() {
+            for archetype in self.query.find_archetypes(&access) {
+                let entities = &access.archetypes()[archetype.0 as usize].entities;
+
+                if !access.archetypes()[archetype.0 as usize].layout.components.contains(&id) {
+                    continue;
+                }
+
+                let (components, ticks) = match (storage.get(archetype), storage.change_ticks(archetype)) {
+                    | (Some(components), Some(ticks)) => (components, ticks),
+                    | _ => continue,
+                };
+
+                for (row, &entity) in entities.iter().enumerate() {
+                    let component = ComponentIndex(row as u32);
+                    let modified = ticks.row(component).is_none_or(|t| t > self.tick);
+
+                    match self.keys.get_mut(&entity) {
+                        | Some((_, epoch)) if !modified => *epoch = self.epoch,
+                        | slot => {
+                            let key = (self.key)(components.get(component).unwrap());
+
+                            match slot {
+                                | Some((old, _)) if *old == key => {},
+                                | Some((old, _)) => {
+                                    removed.push((old.clone(), entity));
+                                    inserted.push((key.clone(), entity));
+                                },
+                                | None => inserted.push((key.clone(), entity)),
+                            }
+
+                            self.keys.insert(entity, (key, self.epoch));
+                        },
+                    }
+                }
+            }
+        }
+
+        let epoch = self.epoch;
+
+        self.keys.retain(|&entity, (key, seen)| {
+            if *seen != epoch {
+                removed.push((key.clone(), entity));
+            }
+
+            *seen == epoch
+        });
+        self.tick = tick;
+
+        if removed.len() + inserted.len() > self.order.len() / 4 {
+            self.order = self.keys.iter().map(|(&e, (k, _))| (k.clone(), e)).collect();
+            self.order.sort_unstable_by(compare);
+            return;
+        }
+
+        for entry in removed {
+            if let Ok(index) = self.order.binary_search_by(|probe| compare(probe, &entry)) {
+                self.order.remove(index);
+            }
+        }
+
+        for entry in inserted {
+            let index = self.order.binary_search_by(|probe| compare(probe, &entry)).unwrap_or_else(|i| i);
+
+            self.order.insert(index, entry);
+        }
+    }
+}
+
+fn compare<K: Ord>(a: &(K, Entity), b: &(K, Entity)) -> CmpOrdering {
+    a.0.cmp(&b.0).then(a.1 .0.cmp(&b.1 .0))
+}
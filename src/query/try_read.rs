@@ -2,14 +2,7 @@ use super::*;
 use crate::filter::Any;
 use crate::resource::TryRead;
 
-pub struct TryReadIter<'a, T: Component> {
-    storage: Option<&'a ArchetypeStorage<T>>,
-    archetypes: &'a [Archetype],
-    index: std::slice::Iter<'a, ArchetypeIndex>,
-    state: TryReadIterState<'a, T>,
-}
-
-pub enum TryReadIterState<'a, T: Component> {
+pub enum TryReadIter<'a, T: Component> {
     Occupied {
         components: <T::Storage as Storage<'a, T>>::Iter,
     },
@@ -30,67 +23,63 @@ impl<'a, T: Component> Fetch<'a> for TryRead<T> {
     type Item = Option<&'a T>;
     type Iter = TryReadIter<'a, T>;
 
-    fn fetch(components: &'a Components, archetypes: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
-        let mut iter = TryReadIter {
-            state: TryReadIterState::Empty { len: 0 },
-            storage: components.get::<T>(),
-            index: index.iter(),
-            archetypes,
-        };
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        match access.components().get::<T>().and_then(|s| s.get(archetype)) {
+            | Some(components) => TryReadIter::Occupied {
+                components: components.iter_range(rows),
+            },
+            | None => TryReadIter::Empty { len: rows.len() },
+        }
+    }
+}
 
-        let _ = iter.next_state();
+impl<'a, T: Component> ChunkFetch<'a> for TryRead<T> {
+    type Chunk = Option<&'a [T]>;
 
-        iter
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        access.components().get::<T>()?.get(archetype).map(|s| s.as_slice())
     }
 }
 
 impl<T: Component> FetchFilter for TryRead<T> {
     type Layout = Any;
+
+    fn access(access: &mut QueryAccess) {
+        access.read::<T>();
+    }
 }
 
 impl<'a, T: Component> Iterator for TryReadIter<'a, T> {
     type Item = Option<&'a T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match &mut self.state {
-            | TryReadIterState::Occupied { components } => match components.next() {
-                | Some(value) => Some(Some(value)),
-                | None => {
-                    self.next_state()?;
-                    self.next()
-                },
-            },
-            | TryReadIterState::Empty { len } => {
-                if *len > 0 {
-                    *len -= 1;
-                    Some(None)
-                } else {
-                    self.next_state()?;
-                    self.next()
-                }
+        match self {
+            | Self::Occupied { components } => components.next().map(Some),
+            | Self::Empty { len } => {
+                *len = len.checked_sub(1)?;
+                Some(None)
             },
         }
     }
-}
-
-impl<'a, T: Component> TryReadIter<'a, T> {
-    fn next_state(&mut self) -> Option<()> {
-        let archetype = *self.index.next()?;
 
-        self.state = match self.storage {
-            | Some(storage) => match storage.get(archetype) {
-                | Some(components) => TryReadIterState::Occupied {
-                    components: components.iter(),
-                },
-                | None => TryReadIterState::Empty {
-                    len: self.archetypes[archetype.0 as usize].entities.len(),
-                },
-            },
-            | None => TryReadIterState::Empty {
-                len: self.archetypes[archetype.0 as usize].entities.len(),
-            },
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self {
+            | Self::Occupied { components } => components.len(),
+            | Self::Empty { len } => *len,
         };
 
-        Some(())
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Component> DoubleEndedIterator for TryReadIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            | Self::Occupied { components } => components.next_back().map(Some),
+            | Self::Empty { .. } => self.next(),
+        }
     }
 }
+
+impl<'a, T: Component> ExactSizeIterator for TryReadIter<'a, T> {
+}
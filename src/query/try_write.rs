@@ -1,17 +1,13 @@
 use super::*;
 use crate::filter::Any;
 use crate::resource::TryWrite;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-pub struct TryWriteIter<'a, T: Component> {
-    storage: Option<&'a ArchetypeStorage<T>>,
-    archetypes: &'a [Archetype],
-    index: std::slice::Iter<'a, ArchetypeIndex>,
-    state: TryWriteIterState<'a, T>,
-}
-
-pub enum TryWriteIterState<'a, T: Component> {
+pub enum TryWriteIter<'a, T: Component> {
     Occupied {
         components: <T::Storage as Storage<'a, T>>::IterMut,
+        ticks: std::slice::Iter<'a, AtomicU64>,
+        tick: u64,
     },
     Empty {
         len: usize,
@@ -30,67 +26,85 @@ impl<'a, T: Component> Fetch<'a> for TryWrite<T> {
     type Item = Option<&'a mut T>;
     type Iter = TryWriteIter<'a, T>;
 
-    fn fetch(components: &'a Components, archetypes: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
-        let mut iter = TryWriteIter {
-            state: TryWriteIterState::Empty { len: 0 },
-            storage: components.get::<T>(),
-            index: index.iter(),
-            archetypes,
-        };
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        let storage = access.components().get::<T>();
+
+        match storage.and_then(|s| s.get_mut_tracked(archetype)) {
+            | Some((components, ticks)) => TryWriteIter::Occupied {
+                tick: ticks.touch(),
+                ticks: ticks.rows()[rows.clone()].iter(),
+                components: unsafe { T::Storage::iter_mut_rows(components, rows) },
+            },
+            | None => TryWriteIter::Empty { len: rows.len() },
+        }
+    }
+}
+
+impl<'a, T: Component> ChunkFetch<'a> for TryWrite<T> {
+    type Chunk = Option<&'a mut [T]>;
 
-        let _ = iter.next_state();
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        let storage = access.components().get::<T>()?;
 
-        iter
+        unsafe { storage.get_mut_unchecked(archetype) }.map(|s| s.as_mut_slice())
     }
 }
 
 impl<T: Component> FetchFilter for TryWrite<T> {
     type Layout = Any;
+
+    fn access(access: &mut QueryAccess) {
+        access.write::<T>();
+    }
 }
 
 impl<'a, T: Component> Iterator for TryWriteIter<'a, T> {
     type Item = Option<&'a mut T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match &mut self.state {
-            | TryWriteIterState::Occupied { components } => match components.next() {
-                | Some(value) => Some(Some(value)),
-                | None => {
-                    self.next_state()?;
-                    self.next()
-                },
-            },
-            | TryWriteIterState::Empty { len } => {
-                if *len > 0 {
-                    *len -= 1;
-                    Some(None)
-                } else {
-                    self.next_state()?;
-                    self.next()
+        match self {
+            | Self::Occupied { components, ticks, tick } => {
+                let value = components.next()?;
+
+                if let Some(row) = ticks.next() {
+                    row.store(*tick, Ordering::Relaxed);
                 }
+
+                Some(Some(value))
+            },
+            | Self::Empty { len } => {
+                *len = len.checked_sub(1)?;
+                Some(None)
             },
         }
     }
-}
 
-impl<'a, T: Component> TryWriteIter<'a, T> {
-    fn next_state(&mut self) -> Option<()> {
-        let archetype = *self.index.next()?;
-
-        self.state = match self.storage {
-            | Some(storage) => match unsafe { storage.get_mut_unchecked(archetype) } {
-                | Some(components) => TryWriteIterState::Occupied {
-                    components: components.iter_mut(),
-                },
-                | None => TryWriteIterState::Empty {
-                    len: self.archetypes[archetype.0 as usize].entities.len(),
-                },
-            },
-            | None => TryWriteIterState::Empty {
-                len: self.archetypes[archetype.0 as usize].entities.len(),
-            },
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self {
+            | Self::Occupied { components, .. } => components.len(),
+            | Self::Empty { len } => *len,
         };
 
-        Some(())
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Component> DoubleEndedIterator for TryWriteIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            | Self::Occupied { components, ticks, tick } => {
+                let value = components.next_back()?;
+
+                if let Some(row) = ticks.next_back() {
+                    row.store(*tick, Ordering::Relaxed);
+                }
+
+                Some(Some(value))
+            },
+            | Self::Empty { .. } => self.next(),
+        }
     }
 }
+
+impl<'a, T: Component> ExactSizeIterator for TryWriteIter<'a, T> {
+}
@@ -1,14 +1,12 @@
 use super::*;
 use crate::filter::Component as ComponentFilter;
 use crate::resource::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-pub enum WriteIter<'a, T: Component> {
-    Empty,
-    Iter {
-        storage: &'a ArchetypeStorage<T>,
-        components: Option<<T::Storage as Storage<'a, T>>::IterMut>,
-        archetypes: std::slice::Iter<'a, ArchetypeIndex>,
-    },
+pub struct WriteIter<'a, T: Component> {
+    components: Option<<T::Storage as Storage<'a, T>>::IterMut>,
+    ticks: std::slice::Iter<'a, AtomicU64>,
+    tick: u64,
 }
 
 impl<T: Component> IntoQuery for &mut T {
@@ -23,45 +21,72 @@ impl<'a, T: Component> Fetch<'a> for Write<T> {
     type Item = &'a mut T;
     type Iter = WriteIter<'a, T>;
 
-    fn fetch(components: &'a Components, _: &'a [Archetype], index: &'a [ArchetypeIndex]) -> Self::Iter {
-        match components.get::<T>() {
-            | None => WriteIter::Empty,
-            | Some(storage) => WriteIter::Iter {
-                storage,
-                components: None,
-                archetypes: index.iter(),
-            },
+    fn fetch(access: StorageAccess<'a>, archetype: ArchetypeIndex, rows: Range<usize>) -> Self::Iter {
+        let mut tick = 0;
+        let mut ticks = [].iter();
+        let storage = access.components().get::<T>();
+        let components = storage
+            .and_then(|s| s.get_mut_tracked(archetype))
+            .map(|(s, t)| {
+                tick = t.touch();
+                ticks = t.rows()[rows.clone()].iter();
+                unsafe { T::Storage::iter_mut_rows(s, rows) }
+            });
+
+        WriteIter { components, ticks, tick }
+    }
+}
+
+impl<'a, T: Component> ChunkFetch<'a> for Write<T> {
+    type Chunk = &'a mut [T];
+
+    fn fetch_chunk(access: StorageAccess<'a>, archetype: ArchetypeIndex) -> Self::Chunk {
+        match access.components().get::<T>().and_then(|s| unsafe { s.get_mut_unchecked(archetype) }) {
+            | Some(components) => components.as_mut_slice(),
+            | None => &mut [],
         }
     }
 }
 
 impl<T: Component> FetchFilter for Write<T> {
     type Layout = ComponentFilter<T>;
+
+    fn access(access: &mut QueryAccess) {
+        access.write::<T>();
+    }
 }
 
 impl<'a, T: Component> Iterator for WriteIter<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            | Self::Empty => None,
-            | Self::Iter {
-                storage,
-                components,
-                archetypes,
-            } => match components {
-                | Some(comps) => match comps.next() {
-                    | Some(comp) => Some(comp),
-                    | None => {
-                        *components = None;
-                        self.next()
-                    },
-                },
-                | None => unsafe {
-                    *components = storage.get_mut_unchecked(*archetypes.next()?).map(|s| s.iter_mut());
-                    self.next()
-                },
-            },
+        let comp = self.components.as_mut()?.next()?;
+
+        if let Some(row) = self.ticks.next() {
+            row.store(self.tick, Ordering::Relaxed);
+        }
+
+        Some(comp)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.components.as_ref().map_or(0, |c| c.len());
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Component> DoubleEndedIterator for WriteIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let comp = self.components.as_mut()?.next_back()?;
+
+        if let Some(row) = self.ticks.next_back() {
+            row.store(self.tick, Ordering::Relaxed);
         }
+
+        Some(comp)
     }
 }
+
+impl<'a, T: Component> ExactSizeIterator for WriteIter<'a, T> {
+}
@@ -0,0 +1,162 @@
+use std::any::{type_name, Any};
+
+pub use ecs_derive::Reflect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectKind {
+    Struct,
+    Enum,
+    Value,
+}
+
+pub trait Reflect: Any {
+    fn type_name(&self) -> &'static str;
+    fn kind(&self) -> ReflectKind;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn variant(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn fields(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn field(&self, _name: &str) -> Option<&dyn Reflect> {
+        None
+    }
+
+    fn field_mut(&mut self, _name: &str) -> Option<&mut dyn Reflect> {
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ReflectFns {
+    get: fn(&dyn Any) -> Option<&dyn Reflect>,
+    get_mut: fn(&mut dyn Any) -> Option<&mut dyn Reflect>,
+}
+
+impl dyn Reflect {
+    pub fn is<T: Reflect>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    pub fn downcast_ref<T: Reflect>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    pub fn downcast_mut<T: Reflect>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+
+    pub fn set<T: Reflect>(&mut self, value: T) -> Result<(), T> {
+        match self.downcast_mut() {
+            | Some(slot) => {
+                *slot = value;
+                Ok(())
+            },
+            | None => Err(value),
+        }
+    }
+
+    pub fn path(&self, path: &str) -> Option<&dyn Reflect> {
+        path.split('.').try_fold(self, |value, name| value.field(name))
+    }
+
+    pub fn path_mut(&mut self, path: &str) -> Option<&mut dyn Reflect> {
+        path.split('.').try_fold(self, |value, name| value.field_mut(name))
+    }
+}
+
+impl ReflectFns {
+    pub(crate) fn of<T: Reflect>() -> Self {
+        Self {
+            get: |any| any.downcast_ref::<T>().map(|t| t as &dyn Reflect),
+            get_mut: |any| any.downcast_mut::<T>().map(|t| t as &mut dyn Reflect),
+        }
+    }
+
+    pub(crate) fn get(self, any: &dyn Any) -> Option<&dyn Reflect> {
+        (self.get)(any)
+    }
+
+    pub(crate) fn get_mut(self, any: &mut dyn Any) -> Option<&mut dyn Reflect> {
+        (self.get_mut)(any)
+    }
+}
+
+impl<T: Reflect> Reflect for Option<T> {
+    fn type_name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn kind(&self) -> ReflectKind {
+        ReflectKind::Enum
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn variant(&self) -> Option<&'static str> {
+        match self {
+            | Some(_) => Some("Some"),
+            | None => Some("None"),
+        }
+    }
+
+    fn fields(&self) -> &'static [&'static str] {
+        match self {
+            | Some(_) => &["0"],
+            | None => &[],
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match (self, name) {
+            | (Some(value), "0") => Some(value),
+            | _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        match (self, name) {
+            | (Some(value), "0") => Some(value),
+            | _ => None,
+        }
+    }
+}
+
+macro_rules! impl_reflect_value {
+    ($($ty:ty),+) => {
+        $(
+            impl Reflect for $ty {
+                fn type_name(&self) -> &'static str {
+                    type_name::<Self>()
+                }
+
+                fn kind(&self) -> ReflectKind {
+                    ReflectKind::Value
+                }
+
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+
+                fn as_any_mut(&mut self) -> &mut dyn Any {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+impl_reflect_value!(
+    bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, String
+);
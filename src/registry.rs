@@ -0,0 +1,258 @@
+use crate::archetype::{ArchetypeIndex, ArchetypeLayout};
+use crate::checksum::HashFn;
+use crate::component::{Component, ComponentId};
+use crate::entity::Entity;
+use crate::hook::{ComponentHooks, DeferredWorld, Hook, HookKind};
+use crate::prefab::CloneFns;
+use crate::reflect::{Reflect, ReflectFns};
+use crate::replication::{Replicate, ReplicateFns};
+use crate::storage::{AnyArchetypeStorage, ArchetypeStorage, Components};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+type InsertFn = Arc<dyn Fn(&mut Components, ArchetypeIndex, usize) + Send + Sync>;
+
+#[derive(Default)]
+pub struct Registry {
+    components: HashMap<ComponentId, ComponentInfo>,
+    replicated: Vec<ComponentId>,
+}
+
+#[derive(Clone)]
+pub struct ComponentInfo {
+    name: Cow<'static, str>,
+    hooks: ComponentHooks,
+    required: Vec<RequiredComponent>,
+    reflect: Option<ReflectFns>,
+    clone: Option<CloneFns>,
+    hash: Option<HashFn>,
+    replicate: Option<ReplicateFns>,
+}
+
+#[derive(Clone)]
+pub(crate) struct RequiredComponent {
+    pub(crate) ty: ComponentId,
+    ctor: fn() -> Box<dyn AnyArchetypeStorage>,
+    insert: InsertFn,
+}
+
+impl Registry {
+    pub fn register<T: Component>(&mut self) -> &mut ComponentInfo {
+        self.components
+            .entry(ComponentId::of::<T>())
+            .or_insert_with(ComponentInfo::new::<T>)
+    }
+
+    pub fn register_reflect<T: Component + Reflect>(&mut self) -> &mut ComponentInfo {
+        let info = self.register::<T>();
+
+        info.reflect = Some(ReflectFns::of::<T>());
+        info
+    }
+
+    pub fn register_clone<T: Component + Clone>(&mut self) -> &mut ComponentInfo {
+        let info = self.register::<T>();
+
+        info.clone = Some(CloneFns::of::<T>());
+        info
+    }
+
+    pub fn register_hash<T: Component + Hash>(&mut self) -> &mut ComponentInfo {
+        let info = self.register::<T>();
+
+        info.hash = Some(|any, mut hasher| any.downcast_ref::<T>().unwrap().hash(&mut hasher));
+        info
+    }
+
+    pub fn register_replicated<T: Component + Replicate>(&mut self) -> &mut ComponentInfo {
+        self.replicate(ComponentId::of::<T>());
+
+        let info = self.register::<T>();
+
+        info.replicate = Some(ReplicateFns::of::<T>());
+        info
+    }
+
+    pub(crate) fn register_replicated_dynamic(&mut self, id: ComponentId, name: &str) -> &mut ComponentInfo {
+        self.replicate(id);
+
+        let info = self.register_dynamic(id, name);
+
+        info.replicate = Some(ReplicateFns::blob());
+        info
+    }
+
+    pub fn replication_id(&self, ty: ComponentId) -> Option<u32> {
+        self.replicated.iter().position(|&id| id == ty).map(|index| index as u32)
+    }
+
+    pub(crate) fn replicated(&self, index: u32) -> Option<(ComponentId, ReplicateFns)> {
+        let id = *self.replicated.get(index as usize)?;
+
+        Some((id, self.get(id)?.replicate_fns()?))
+    }
+
+    fn replicate(&mut self, ty: ComponentId) {
+        if !self.replicated.contains(&ty) {
+            self.replicated.push(ty);
+        }
+    }
+
+    pub(crate) fn register_dynamic(&mut self, id: ComponentId, name: &str) -> &mut ComponentInfo {
+        self.components
+            .entry(id)
+            .or_insert_with(|| ComponentInfo::with_name(Cow::Owned(name.to_owned())))
+    }
+
+    pub(crate) fn adopt(&mut self, ty: ComponentId, info: &ComponentInfo) {
+        self.components.entry(ty).or_insert_with(|| info.clone());
+    }
+
+    pub fn get(&self, ty: ComponentId) -> Option<&ComponentInfo> {
+        self.components.get(&ty)
+    }
+
+    pub fn contains(&self, ty: ComponentId) -> bool {
+        self.components.contains_key(&ty)
+    }
+
+    pub fn find(&self, name: &str) -> Option<(ComponentId, &ComponentInfo)> {
+        self.components
+            .iter()
+            .find(|(_, info)| info.name() == name)
+            .map(|(&id, info)| (id, info))
+    }
+
+    pub(crate) fn hooks(&self, kind: HookKind, components: &[ComponentId]) -> Vec<Hook> {
+        components
+            .iter()
+            .filter_map(|ty| self.components.get(ty))
+            .filter_map(|info| info.hooks.get(kind).cloned())
+            .collect()
+    }
+
+    pub(crate) fn require(&self, layout: &mut ArchetypeLayout) -> Vec<RequiredComponent> {
+        let mut required = Vec::new();
+        let mut pending = layout.components.clone();
+
+        while let Some(ty) = pending.pop() {
+            for req in self.components.get(&ty).into_iter().flat_map(|info| &info.required) {
+                if !layout.components.contains(&req.ty) {
+                    layout.add_any(req.ty, req.ctor);
+                    pending.push(req.ty);
+                    required.push(req.clone());
+                }
+            }
+        }
+
+        required
+    }
+}
+
+impl ComponentInfo {
+    fn new<T: Component>() -> Self {
+        Self::with_name(Cow::Borrowed(type_name::<T>()))
+    }
+
+    fn with_name(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            hooks: ComponentHooks::default(),
+            required: Vec::new(),
+            reflect: None,
+            clone: None,
+            hash: None,
+            replicate: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_reflect(&self) -> bool {
+        self.reflect.is_some()
+    }
+
+    pub(crate) fn reflect_fns(&self) -> Option<ReflectFns> {
+        self.reflect
+    }
+
+    pub fn is_clone(&self) -> bool {
+        self.clone.is_some()
+    }
+
+    pub(crate) fn clone_fns(&self) -> Option<CloneFns> {
+        self.clone
+    }
+
+    pub(crate) fn set_clone_fns(&mut self, fns: CloneFns) -> &mut Self {
+        self.clone = Some(fns);
+        self
+    }
+
+    pub fn is_hash(&self) -> bool {
+        self.hash.is_some()
+    }
+
+    pub(crate) fn hash_fn(&self) -> Option<HashFn> {
+        self.hash
+    }
+
+    pub fn is_replicated(&self) -> bool {
+        self.replicate.is_some()
+    }
+
+    pub(crate) fn replicate_fns(&self) -> Option<ReplicateFns> {
+        self.replicate
+    }
+
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    pub fn on_add<F: Fn(&mut DeferredWorld<'_>, Entity) + Send + Sync + 'static>(&mut self, hook: F) -> &mut Self {
+        self.hooks.on_add = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn on_insert<F: Fn(&mut DeferredWorld<'_>, Entity) + Send + Sync + 'static>(&mut self, hook: F) -> &mut Self {
+        self.hooks.on_insert = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn on_remove<F: Fn(&mut DeferredWorld<'_>, Entity) + Send + Sync + 'static>(&mut self, hook: F) -> &mut Self {
+        self.hooks.on_remove = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn require<R: Component + Default>(&mut self) -> &mut Self {
+        self.require_with(R::default)
+    }
+
+    pub fn require_with<R: Component, F: Fn() -> R + Send + Sync + 'static>(&mut self, ctor: F) -> &mut Self {
+        let ty = ComponentId::of::<R>();
+
+        self.required.retain(|req| req.ty != ty);
+        self.required.push(RequiredComponent {
+            ty,
+            ctor: ArchetypeStorage::<R>::any,
+            insert: Arc::new(move |components, archetype, count| {
+                if let Some(storage) = components.get_mut::<R>() {
+                    storage.extend(archetype, (0..count).map(|_| ctor()));
+                }
+            }),
+        });
+
+        self
+    }
+}
+
+impl RequiredComponent {
+    pub(crate) fn insert(&self, components: &mut Components, archetype: ArchetypeIndex, count: usize) {
+        (self.insert)(components, archetype, count)
+    }
+}
@@ -0,0 +1,304 @@
+use crate::archetype::ArchetypeLayout;
+use crate::component::{Component, ComponentId};
+use crate::entity::Entity;
+use crate::storage::VecStorage;
+use crate::subworld::AnyWorld;
+use crate::world::World;
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+pub struct Relations<R> {
+    targets: Vec<(Entity, R)>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct RelationIndex {
+    sources: HashMap<Entity, Vec<RelationEdge>>,
+    targets: HashMap<Entity, Vec<(TypeId, Entity)>>,
+}
+
+#[derive(Clone)]
+pub(crate) struct RelationEdge {
+    kind: TypeId,
+    pub(crate) component: ComponentId,
+    source: Entity,
+    unlink: fn(&mut World, Entity, Entity),
+    remap: fn(&mut World, Entity, &HashMap<Entity, Entity>),
+}
+
+impl<R: Clone> Clone for Relations<R> {
+    fn clone(&self) -> Self {
+        Self {
+            targets: self.targets.clone(),
+        }
+    }
+}
+
+impl<R: Send + Sync + 'static> Component for Relations<R> {
+    type Storage = VecStorage<Self>;
+}
+
+impl<R> Relations<R> {
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub fn contains(&self, target: Entity) -> bool {
+        self.targets.iter().any(|(t, _)| *t == target)
+    }
+
+    pub fn first(&self) -> Option<Entity> {
+        self.targets.first().map(|(t, _)| *t)
+    }
+
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.targets.iter().map(|(t, _)| *t)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<(Entity, R)> {
+        self.targets.iter()
+    }
+
+    pub fn get(&self, target: Entity) -> Option<&R> {
+        self.targets.iter().find(|(t, _)| *t == target).map(|(_, r)| r)
+    }
+
+    pub fn get_mut(&mut self, target: Entity) -> Option<&mut R> {
+        self.targets.iter_mut().find(|(t, _)| *t == target).map(|(_, r)| r)
+    }
+
+    fn insert(&mut self, target: Entity, relation: R) {
+        match self.get_mut(target) {
+            | Some(slot) => *slot = relation,
+            | None => self.targets.push((target, relation)),
+        }
+    }
+
+    fn remove(&mut self, target: Entity) -> Option<R> {
+        let idx = self.targets.iter().position(|(t, _)| *t == target)?;
+
+        Some(self.targets.remove(idx).1)
+    }
+}
+
+impl RelationIndex {
+    fn link<R: Component>(&mut self, source: Entity, target: Entity) {
+        let edge = RelationEdge {
+            kind: TypeId::of::<R>(),
+            component: ComponentId::of::<Relations<R>>(),
+            source,
+            unlink: unlink::<R>,
+            remap: remap::<R>,
+        };
+
+        self.insert(edge, target);
+    }
+
+    fn insert(&mut self, edge: RelationEdge, target: Entity) {
+        let targets = self.targets.entry(edge.source).or_default();
+
+        if !targets.contains(&(edge.kind, target)) {
+            targets.push((edge.kind, target));
+            self.sources.entry(target).or_default().push(edge);
+        }
+    }
+
+    fn unlink(&mut self, kind: TypeId, source: Entity, target: Entity) {
+        if let Some(targets) = self.targets.get_mut(&source) {
+            targets.retain(|&t| t != (kind, target));
+        }
+
+        if let Some(sources) = self.sources.get_mut(&target) {
+            sources.retain(|e| e.kind != kind || e.source != source);
+        }
+    }
+}
+
+impl World {
+    pub fn add_relation<R: Component>(&mut self, source: Entity, relation: R, target: Entity) -> bool {
+        if !self.contains(target) {
+            return false;
+        }
+
+        let mut entry = match self.entry(source) {
+            | Some(entry) => entry,
+            | None => return false,
+        };
+
+        match entry.component_mut::<Relations<R>>() {
+            | Some(relations) => relations.insert(target, relation),
+            | None => entry.add_component(Relations {
+                targets: vec![(target, relation)],
+            }),
+        }
+
+        drop(entry);
+        self.relations.link::<R>(source, target);
+        true
+    }
+
+    pub fn remove_relation<R: Component>(&mut self, source: Entity, target: Entity) -> Option<R> {
+        let mut entry = self.entry(source)?;
+        let relations = entry.component_mut::<Relations<R>>()?;
+        let relation = relations.remove(target)?;
+
+        if relations.is_empty() {
+            entry.remove_component::<Relations<R>>();
+        }
+
+        drop(entry);
+        self.relations.unlink(TypeId::of::<R>(), source, target);
+        Some(relation)
+    }
+
+    pub fn relations<R: Component>(&self, source: Entity) -> Option<&Relations<R>> {
+        self.storage_access().component(source)
+    }
+
+    pub fn sources<R: Component>(&self, target: Entity) -> impl Iterator<Item = Entity> + '_ {
+        let kind = TypeId::of::<R>();
+
+        self.relations
+            .sources
+            .get(&target)
+            .into_iter()
+            .flatten()
+            .filter(move |e| e.kind == kind)
+            .map(|e| e.source)
+    }
+
+    pub(crate) fn unlink_relations(&mut self, entity: Entity) {
+        for (kind, target) in self.relations.targets.remove(&entity).unwrap_or_default() {
+            if let Some(sources) = self.relations.sources.get_mut(&target) {
+                sources.retain(|e| e.kind != kind || e.source != entity);
+            }
+        }
+
+        for edge in self.relations.sources.remove(&entity).unwrap_or_default() {
+            (edge.unlink)(self, edge.source, entity);
+        }
+    }
+
+    pub(crate) fn sever_relations(&mut self, entity: Entity) {
+        for (kind, target) in self.relations.targets.get(&entity).cloned().unwrap_or_default() {
+            let unlink = self
+                .relations
+                .sources
+                .get(&target)
+                .and_then(|edges| edges.iter().find(|e| e.kind == kind && e.source == entity))
+                .map(|e| e.unlink);
+
+            if let Some(unlink) = unlink {
+                unlink(self, entity, target);
+            }
+        }
+
+        self.unlink_relations(entity);
+    }
+
+    pub(crate) fn severed_layout(&self, entity: Entity, layout: &ArchetypeLayout) -> ArchetypeLayout {
+        let mut layout = layout.clone();
+
+        for &(kind, target) in self.relations.targets.get(&entity).into_iter().flatten() {
+            let edge = self
+                .relations
+                .sources
+                .get(&target)
+                .and_then(|edges| edges.iter().find(|e| e.kind == kind && e.source == entity));
+
+            if let Some(edge) = edge {
+                layout.remove(edge.component);
+            }
+        }
+
+        layout
+    }
+
+    pub(crate) fn outgoing_relations(&self, entity: Entity) -> Vec<(RelationEdge, Entity)> {
+        let mut edges = Vec::new();
+
+        for &(kind, target) in self.relations.targets.get(&entity).into_iter().flatten() {
+            let edge = self
+                .relations
+                .sources
+                .get(&target)
+                .and_then(|edges| edges.iter().find(|e| e.kind == kind && e.source == entity));
+
+            if let Some(edge) = edge {
+                edges.push((edge.clone(), target));
+            }
+        }
+
+        edges
+    }
+
+    pub(crate) fn relink(&mut self, source: Entity, edges: &[(RelationEdge, Entity)]) {
+        for (edge, target) in edges {
+            if self.contains(*target) {
+                self.relations.insert(RelationEdge { source, ..edge.clone() }, *target);
+            } else {
+                (edge.unlink)(self, source, *target);
+            }
+        }
+    }
+
+    pub(crate) fn adopt_relations(&mut self, relations: RelationIndex, entities: &HashMap<Entity, Entity>) {
+        let mut remapped = HashSet::new();
+
+        for (target, edges) in relations.sources {
+            for edge in edges {
+                let (source, target) = match (entities.get(&edge.source), entities.get(&target)) {
+                    | (Some(&source), Some(&target)) => (source, target),
+                    | _ => continue,
+                };
+
+                if remapped.insert((edge.kind, source)) {
+                    (edge.remap)(self, source, entities);
+                }
+
+                self.relations.insert(RelationEdge { source, ..edge }, target);
+            }
+        }
+    }
+
+    pub(crate) fn split_relations(&mut self, other: &mut World, moved: &HashSet<Entity>) {
+        let sources = std::mem::take(&mut self.relations.sources);
+        let mut cut = Vec::new();
+
+        self.relations.targets.clear();
+
+        for (target, edges) in sources {
+            for edge in edges {
+                match (moved.contains(&edge.source), moved.contains(&target)) {
+                    | (true, true) => other.relations.insert(edge, target),
+                    | (false, false) => self.relations.insert(edge, target),
+                    | _ => cut.push((edge, target)),
+                }
+            }
+        }
+
+        for (edge, target) in cut {
+            let world = if moved.contains(&edge.source) { &mut *other } else { &mut *self };
+
+            (edge.unlink)(world, edge.source, target);
+        }
+    }
+}
+
+fn unlink<R: Component>(world: &mut World, source: Entity, target: Entity) {
+    let _ = world.remove_relation::<R>(source, target);
+}
+
+fn remap<R: Component>(world: &mut World, source: Entity, entities: &HashMap<Entity, Entity>) {
+    if let Some(relations) = world.component_mut::<Relations<R>>(source) {
+        for (target, _) in &mut relations.targets {
+            if let Some(&mapped) = entities.get(target) {
+                *target = mapped;
+            }
+        }
+    }
+}
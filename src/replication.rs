@@ -0,0 +1,414 @@
+use crate::archetype::ArchetypeIndex;
+use crate::component::{Component, ComponentId, ComponentIndex};
+use crate::entity::Entity;
+use crate::error::EcsError;
+use crate::storage::{change_tick, AnyArchetypeStorage};
+use crate::world::World;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+
+pub trait Replicate: Component + Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ReplicateFns {
+    encode: fn(&dyn AnyArchetypeStorage, ArchetypeIndex, ComponentIndex, &mut Vec<u8>),
+    validate: fn(&World, ComponentId, &[u8]) -> Result<(), EcsError>,
+    apply: fn(&mut World, ComponentId, Entity, &[u8]) -> Result<(), EcsError>,
+    remove: fn(&mut World, ComponentId, Entity),
+}
+
+#[derive(Default)]
+pub struct DeltaEncoder {
+    tick: u64,
+    entities: HashMap<Entity, Vec<ComponentId>>,
+}
+
+#[derive(Default)]
+pub struct DeltaDecoder {
+    entities: HashMap<Entity, Entity>,
+}
+
+#[derive(Default)]
+struct Delta<'a> {
+    despawned: Vec<Entity>,
+    spawned: Vec<Entity>,
+    removed: Vec<(Entity, u32)>,
+    changed: Vec<(Entity, u32, &'a [u8])>,
+}
+
+struct Reader<'a>(&'a [u8]);
+
+macro_rules! impl_replicate {
+    ($($ty:ty)*) => {
+        $(
+            impl Replicate for $ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Option<Self> {
+                    Some(<$ty>::from_le_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_replicate!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64);
+
+impl Replicate for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            | [0] => Some(false),
+            | [1] => Some(true),
+            | _ => None,
+        }
+    }
+}
+
+impl Replicate for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+impl ReplicateFns {
+    pub(crate) fn of<T: Replicate>() -> Self {
+        Self {
+            encode: |storage, archetype, component, out| {
+                if let Some(value) = storage.get_any(archetype, component).and_then(|any| any.downcast_ref::<T>()) {
+                    value.encode(out);
+                }
+            },
+            validate: |_, _, bytes| decode::<T>(bytes).map(drop),
+            apply: |world, _, entity, bytes| {
+                let value = decode::<T>(bytes)?;
+
+                world.try_entry(entity)?.try_add_component(value)
+            },
+            remove: |world, _, entity| {
+                if let Some(mut entry) = world.entry(entity) {
+                    entry.remove_component::<T>();
+                }
+            },
+        }
+    }
+
+    pub(crate) fn blob() -> Self {
+        Self {
+            encode: |storage, archetype, component, out| {
+                if let Some(bytes) = storage.get_bytes(archetype, component) {
+                    out.extend_from_slice(bytes);
+                }
+            },
+            validate: |world, id, bytes| world.check_dynamic(id, bytes).map(drop),
+            apply: |world, id, entity, bytes| {
+                // `register_replicated_dynamic` only accepts descriptors without a drop fn.
+                unsafe { world.try_entry(entity)?.try_add_dynamic(id, bytes) }
+            },
+            remove: |world, id, entity| {
+                if let Some(mut entry) = world.entry(entity) {
+                    entry.remove_dynamic(id);
+                }
+            },
+        }
+    }
+}
+
+impl DeltaEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn encode(&mut self, world: &World) -> Vec<u8> {
+        self.encode_since(world, self.tick)
+    }
+
+    pub fn encode_since(&mut self, world: &World, since: u64) -> Vec<u8> {
+        let tick = change_tick();
+        let mut delta = Delta::default();
+        let mut values = Vec::new();
+        let mut changed = Vec::new();
+        let mut entities = HashMap::with_capacity(self.entities.len());
+
+        for archetype in &world.archetypes {
+            let columns = archetype
+                .layout
+                .components
+                .iter()
+                .filter_map(|&id| {
+                    let index = world.registry().replication_id(id)?;
+                    let fns = world.registry().get(id)?.replicate_fns()?;
+                    let storage = world.components.get_any(id)?;
+
+                    Some((id, index, storage, fns))
+                })
+                .collect::<Vec<_>>();
+
+            for (row, &entity) in archetype.entities.iter().enumerate() {
+                let component = ComponentIndex(row as u32);
+                let previous = self.entities.remove(&entity);
+
+                if previous.is_none() {
+                    delta.spawned.push(entity);
+                }
+
+                for &(id, index, storage, fns) in &columns {
+                    let known = previous.as_ref().is_some_and(|p| p.contains(&id));
+                    let modified = storage.component_tick(archetype.index, component).is_none_or(|t| t > since);
+
+                    if !known || modified {
+                        let start = values.len();
+
+                        (fns.encode)(storage, archetype.index, component, &mut values);
+                        changed.push((entity, index, start..values.len()));
+                    }
+                }
+
+                for id in previous.iter().flatten().filter(|id| !columns.iter().any(|c| c.0 == **id)) {
+                    if let Some(index) = world.registry().replication_id(*id) {
+                        delta.removed.push((entity, index));
+                    }
+                }
+
+                entities.insert(entity, columns.iter().map(|c| c.0).collect());
+            }
+        }
+
+        delta.despawned = self.entities.drain().map(|(entity, _)| entity).collect();
+        delta.despawned.sort_unstable_by_key(|e| e.0);
+
+        self.entities = entities;
+        self.tick = tick;
+
+        delta.changed = changed
+            .into_iter()
+            .map(|(entity, index, range)| (entity, index, &values[range]))
+            .collect();
+        delta.write()
+    }
+}
+
+impl DeltaDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, remote: Entity) -> Option<Entity> {
+        self.entities.get(&remote).copied()
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.entities.iter().map(|(&remote, &local)| (remote, local))
+    }
+
+    /// Applies a delta from [`DeltaEncoder::encode`]. The whole delta is checked before the world is touched, so a
+    /// malformed delta changes nothing; only a failed insert, such as a full single storage, can leave it partly
+    /// applied.
+    pub fn apply(&mut self, world: &mut World, bytes: &[u8]) -> Result<(), EcsError> {
+        let delta = Delta::read(bytes)?;
+
+        self.validate(world, &delta)?;
+
+        for remote in delta.despawned {
+            if let Some(local) = self.entities.remove(&remote) {
+                world.remove(local);
+            }
+        }
+
+        for remote in delta.spawned {
+            if !self.get(remote).is_some_and(|local| world.contains(local)) {
+                self.entities.insert(remote, world.create(()));
+            }
+        }
+
+        for (remote, index) in delta.removed {
+            let (id, fns) = replicated(world, index)?;
+
+            (fns.remove)(world, id, self.local(remote)?);
+        }
+
+        for (remote, index, bytes) in delta.changed {
+            let (id, fns) = replicated(world, index)?;
+
+            (fns.apply)(world, id, self.local(remote)?, bytes)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self, world: &World, delta: &Delta<'_>) -> Result<(), EcsError> {
+        let spawned = delta.spawned.iter().collect::<HashSet<_>>();
+        let despawned = delta.despawned.iter().collect::<HashSet<_>>();
+        let known = |remote: &Entity| {
+            if spawned.contains(remote)
+                || !despawned.contains(remote) && self.get(*remote).is_some_and(|local| world.contains(local))
+            {
+                Ok(())
+            } else {
+                Err(EcsError::MalformedDelta {
+                    reason: "entity was never spawned",
+                })
+            }
+        };
+
+        for (remote, index) in &delta.removed {
+            replicated(world, *index)?;
+            known(remote)?;
+        }
+
+        for (remote, index, bytes) in &delta.changed {
+            let (id, fns) = replicated(world, *index)?;
+
+            known(remote)?;
+            (fns.validate)(world, id, bytes)?;
+        }
+
+        Ok(())
+    }
+
+    fn local(&self, remote: Entity) -> Result<Entity, EcsError> {
+        self.get(remote).ok_or(EcsError::MalformedDelta {
+            reason: "entity was never spawned",
+        })
+    }
+}
+
+impl<'a> Delta<'a> {
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        for entities in [&self.despawned, &self.spawned].iter() {
+            write_varint(&mut out, entities.len() as u64);
+
+            for entity in entities.iter() {
+                write_varint(&mut out, entity.0);
+            }
+        }
+
+        write_varint(&mut out, self.removed.len() as u64);
+
+        for &(entity, component) in &self.removed {
+            write_varint(&mut out, entity.0);
+            write_varint(&mut out, component as u64);
+        }
+
+        write_varint(&mut out, self.changed.len() as u64);
+
+        for &(entity, component, bytes) in &self.changed {
+            write_varint(&mut out, entity.0);
+            write_varint(&mut out, component as u64);
+            write_bytes(&mut out, bytes);
+        }
+
+        out
+    }
+
+    fn read(bytes: &'a [u8]) -> Result<Self, EcsError> {
+        let mut reader = Reader(bytes);
+        let mut delta = Delta::default();
+
+        for _ in 0..reader.varint()? {
+            delta.despawned.push(Entity(reader.varint()?));
+        }
+
+        for _ in 0..reader.varint()? {
+            delta.spawned.push(Entity(reader.varint()?));
+        }
+
+        for _ in 0..reader.varint()? {
+            delta.removed.push((Entity(reader.varint()?), reader.varint()? as u32));
+        }
+
+        for _ in 0..reader.varint()? {
+            delta.changed.push((Entity(reader.varint()?), reader.varint()? as u32, reader.bytes()?));
+        }
+
+        if !reader.0.is_empty() {
+            return Err(EcsError::MalformedDelta {
+                reason: "trailing bytes",
+            });
+        }
+
+        Ok(delta)
+    }
+}
+
+impl<'a> Reader<'a> {
+    fn varint(&mut self) -> Result<u64, EcsError> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self.0.split_first().ok_or(EcsError::MalformedDelta {
+                reason: "unexpected end of input",
+            })?;
+
+            self.0 = rest;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(EcsError::MalformedDelta {
+            reason: "varint is too long",
+        })
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], EcsError> {
+        let len = self.varint()? as usize;
+
+        if len > self.0.len() {
+            return Err(EcsError::MalformedDelta {
+                reason: "unexpected end of input",
+            });
+        }
+
+        let (bytes, rest) = self.0.split_at(len);
+
+        self.0 = rest;
+        Ok(bytes)
+    }
+}
+
+fn replicated(world: &World, index: u32) -> Result<(ComponentId, ReplicateFns), EcsError> {
+    world.registry().replicated(index).ok_or_else(|| EcsError::NotReplicated {
+        component: format!("#{}", index),
+    })
+}
+
+fn decode<T: Replicate>(bytes: &[u8]) -> Result<T, EcsError> {
+    T::decode(bytes).ok_or(EcsError::MalformedDelta {
+        reason: "component value failed to decode",
+    })
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    out.push(value as u8);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
@@ -1,22 +1,81 @@
 pub use atomic_refcell::{AtomicRef, AtomicRefMut};
 
+use crate::error::EcsError;
+
 use atomic_refcell::AtomicRefCell;
 use std::{
     any::{type_name, Any, TypeId},
+    cell::RefCell,
     collections::HashMap,
     marker::PhantomData,
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+    thread::{self, ThreadId},
 };
 
+static NEXT_SCOPE: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static LIVE_SCOPES: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
 #[derive(Default)]
 pub struct Resources {
     resources: HashMap<TypeId, AtomicRefCell<Box<dyn Resource>>>,
+    scoped: HashMap<TypeId, ScopedSlot>,
+    non_send: NonSendResources,
+}
+
+struct ScopedSlot {
+    ptr: ScopedPtr,
+    owner: ThreadId,
+    scope: u64,
+    borrow: AtomicRefCell<()>,
+}
+
+#[derive(Clone, Copy)]
+struct ScopedPtr(NonNull<u8>);
+
+struct ScopeGuard<'a> {
+    resources: &'a mut Resources,
+    id: TypeId,
+    scope: u64,
+    previous: Option<ScopedSlot>,
+}
+
+pub struct ScopedRef<'resources, K> {
+    resources: &'resources Resources,
+    _marker: PhantomData<K>,
 }
 
-pub trait Resource: Any {}
+pub struct ScopedMut<'resources, K> {
+    resources: &'resources Resources,
+    _marker: PhantomData<K>,
+}
+
+#[derive(Default)]
+struct NonSendResources {
+    owner: Option<ThreadId>,
+    resources: HashMap<TypeId, AtomicRefCell<Box<dyn Any>>>,
+}
+
+pub trait Resource: Any + Send + Sync {}
+
+/// Marker for a borrowed, non-`'static` value inserted with [`Resources::scope`].
+///
+/// `Read`/`Write` hand out guards keyed by the resource's own `TypeId`, which a borrowed type does not have, and
+/// those guards could outlive the scope. Scoped values are keyed by the marker instead and only lent to closures.
+pub trait ScopedResource: 'static {
+    type Target<'a>;
+}
 
 pub trait ResourceSet<'resources> {
     type Result: 'resources;
 
+    /// # Safety
+    ///
+    /// Resources fetched mutably must not be borrowed elsewhere while the result is alive. [`ResourceSet::fetch`]
+    /// and [`ResourceSet::fetch_mut`] uphold this through `Readonly` or exclusive access.
     unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result;
 
     fn fetch(resources: &'resources Resources) -> Self::Result
@@ -29,6 +88,10 @@ pub trait ResourceSet<'resources> {
     fn fetch_mut(resources: &'resources mut Resources) -> Self::Result {
         unsafe { Self::fetch_unchecked(resources) }
     }
+
+    fn thread_local() -> bool {
+        false
+    }
 }
 
 pub trait Readonly {}
@@ -39,12 +102,22 @@ pub struct Write<T>(PhantomData<*mut T>);
 pub struct TryRead<T>(PhantomData<Option<*const T>>);
 pub struct TryWrite<T>(PhantomData<Option<*mut T>>);
 
+pub struct ReadNonSend<T>(PhantomData<*const T>);
+pub struct WriteNonSend<T>(PhantomData<*mut T>);
+
+pub struct ReadScoped<K>(PhantomData<*const K>);
+pub struct WriteScoped<K>(PhantomData<*mut K>);
+
 impl<T> Readonly for Read<T> {
 }
 impl<T> Readonly for TryRead<T> {
 }
+impl<T> Readonly for ReadNonSend<T> {
+}
+impl<K> Readonly for ReadScoped<K> {
+}
 
-impl<T: 'static> Resource for T {
+impl<T: Send + Sync + 'static> Resource for T {
 }
 
 impl<'resources> ResourceSet<'resources> for () {
@@ -75,7 +148,7 @@ impl<'resources, T: Resource> ResourceSet<'resources> for TryRead<T> {
     type Result = Option<AtomicRef<'resources, T>>;
 
     unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
-        resources.try_get()
+        resources.get_optional()
     }
 }
 
@@ -83,7 +156,61 @@ impl<'resources, T: Resource> ResourceSet<'resources> for TryWrite<T> {
     type Result = Option<AtomicRefMut<'resources, T>>;
 
     unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
-        resources.try_get_mut()
+        resources.get_mut_optional()
+    }
+}
+
+impl<'resources, T: 'static> ResourceSet<'resources> for ReadNonSend<T> {
+    type Result = AtomicRef<'resources, T>;
+
+    unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
+        resources.get_non_send()
+    }
+
+    fn thread_local() -> bool {
+        true
+    }
+}
+
+impl<'resources, T: 'static> ResourceSet<'resources> for WriteNonSend<T> {
+    type Result = AtomicRefMut<'resources, T>;
+
+    unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
+        resources.get_non_send_mut()
+    }
+
+    fn thread_local() -> bool {
+        true
+    }
+}
+
+impl<'resources, K: ScopedResource> ResourceSet<'resources> for ReadScoped<K> {
+    type Result = ScopedRef<'resources, K>;
+
+    unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
+        ScopedRef {
+            resources,
+            _marker: PhantomData,
+        }
+    }
+
+    fn thread_local() -> bool {
+        true
+    }
+}
+
+impl<'resources, K: ScopedResource> ResourceSet<'resources> for WriteScoped<K> {
+    type Result = ScopedMut<'resources, K>;
+
+    unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
+        ScopedMut {
+            resources,
+            _marker: PhantomData,
+        }
+    }
+
+    fn thread_local() -> bool {
+        true
     }
 }
 
@@ -93,12 +220,22 @@ impl<'resources> ResourceSet<'resources> for Resources {
     unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
         resources
     }
+
+    fn thread_local() -> bool {
+        true
+    }
+}
+
+unsafe impl Send for ScopedPtr {
 }
 
-unsafe impl Send for Resources {
+unsafe impl Sync for ScopedPtr {
 }
 
-unsafe impl Sync for Resources {
+unsafe impl Send for NonSendResources {
+}
+
+unsafe impl Sync for NonSendResources {
 }
 
 impl Resources {
@@ -120,27 +257,51 @@ impl Resources {
     }
 
     pub fn get<T: Resource>(&self) -> AtomicRef<T> {
-        self.try_get()
-            .expect(&format!("Resource `{}` not available", type_name::<T>()))
+        self.try_get().unwrap_or_else(|err| panic!("{}", err))
     }
 
     pub fn get_mut<T: Resource>(&self) -> AtomicRefMut<T> {
-        self.try_get_mut()
-            .expect(&format!("Resource `{}` not available", type_name::<T>()))
+        self.try_get_mut().unwrap_or_else(|err| panic!("{}", err))
     }
 
-    pub fn try_get<T: Resource>(&self) -> Option<AtomicRef<T>> {
-        self.resources.get(&TypeId::of::<T>()).map(|v| {
-            let borrow = v.borrow();
-            AtomicRef::map(borrow, |v| v.downcast_ref().unwrap())
-        })
+    pub fn try_get<T: Resource>(&self) -> Result<AtomicRef<T>, EcsError> {
+        let resource = type_name::<T>();
+        let borrow = self
+            .resources
+            .get(&TypeId::of::<T>())
+            .ok_or(EcsError::MissingResource { resource })?
+            .try_borrow()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(AtomicRef::map(borrow, |v| v.downcast_ref().unwrap()))
     }
 
-    pub fn try_get_mut<T: Resource>(&self) -> Option<AtomicRefMut<T>> {
-        self.resources.get(&TypeId::of::<T>()).map(|v| {
-            let borrow = v.borrow_mut();
-            AtomicRefMut::map(borrow, |v| v.downcast_mut().unwrap())
-        })
+    pub fn try_get_mut<T: Resource>(&self) -> Result<AtomicRefMut<T>, EcsError> {
+        let resource = type_name::<T>();
+        let borrow = self
+            .resources
+            .get(&TypeId::of::<T>())
+            .ok_or(EcsError::MissingResource { resource })?
+            .try_borrow_mut()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(AtomicRefMut::map(borrow, |v| v.downcast_mut().unwrap()))
+    }
+
+    pub fn get_optional<T: Resource>(&self) -> Option<AtomicRef<T>> {
+        match self.try_get() {
+            | Ok(resource) => Some(resource),
+            | Err(EcsError::MissingResource { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn get_mut_optional<T: Resource>(&self) -> Option<AtomicRefMut<T>> {
+        match self.try_get_mut() {
+            | Ok(resource) => Some(resource),
+            | Err(EcsError::MissingResource { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
     }
 
     pub fn get_or_insert_with<T: Resource, F: FnOnce() -> T>(&mut self, insert: F) -> AtomicRef<T> {
@@ -178,6 +339,245 @@ impl Resources {
     pub fn get_mut_or_default<T: Resource + Default>(&mut self) -> AtomicRefMut<T> {
         self.get_mut_or_insert_with(T::default)
     }
+
+    pub fn scope<K: ScopedResource, R, F: FnOnce(&mut Resources) -> R>(
+        &mut self,
+        resource: &mut K::Target<'_>,
+        f: F,
+    ) -> R {
+        let id = TypeId::of::<K>();
+        let scope = NEXT_SCOPE.fetch_add(1, Ordering::Relaxed);
+        let slot = ScopedSlot {
+            ptr: ScopedPtr(NonNull::from(resource).cast()),
+            owner: thread::current().id(),
+            scope,
+            borrow: AtomicRefCell::new(()),
+        };
+
+        LIVE_SCOPES.with(|live| live.borrow_mut().push(scope));
+
+        let previous = self.scoped.insert(id, slot);
+        let guard = ScopeGuard {
+            resources: self,
+            id,
+            scope,
+            previous,
+        };
+
+        f(guard.resources)
+    }
+
+    pub fn contains_scoped<K: ScopedResource>(&self) -> bool {
+        self.scoped.contains_key(&TypeId::of::<K>())
+    }
+
+    pub fn with_scoped<K: ScopedResource, R, F>(&self, f: F) -> R
+    where
+        F: for<'a> FnOnce(&K::Target<'a>) -> R,
+    {
+        self.try_with_scoped::<K, R, F>(f).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn with_scoped_mut<K: ScopedResource, R, F>(&self, f: F) -> R
+    where
+        F: for<'a> FnOnce(&mut K::Target<'a>) -> R,
+    {
+        self.try_with_scoped_mut::<K, R, F>(f).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_scoped<K: ScopedResource, R, F>(&self, f: F) -> Result<R, EcsError>
+    where
+        F: for<'a> FnOnce(&K::Target<'a>) -> R,
+    {
+        let resource = type_name::<K>();
+        let slot = self.scoped_slot::<K>()?;
+        let _borrow = slot
+            .borrow
+            .try_borrow()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(f(unsafe { &*(slot.ptr.0.as_ptr() as *const K::Target<'_>) }))
+    }
+
+    pub fn try_with_scoped_mut<K: ScopedResource, R, F>(&self, f: F) -> Result<R, EcsError>
+    where
+        F: for<'a> FnOnce(&mut K::Target<'a>) -> R,
+    {
+        let resource = type_name::<K>();
+        let slot = self.scoped_slot::<K>()?;
+        let _borrow = slot
+            .borrow
+            .try_borrow_mut()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(f(unsafe { &mut *(slot.ptr.0.as_ptr() as *mut K::Target<'_>) }))
+    }
+
+    fn scoped_slot<K: ScopedResource>(&self) -> Result<&ScopedSlot, EcsError> {
+        let resource = type_name::<K>();
+        let slot = self
+            .scoped
+            .get(&TypeId::of::<K>())
+            .ok_or(EcsError::MissingResource { resource })?;
+
+        if slot.owner != thread::current().id() {
+            return Err(EcsError::NonSendAccess { resource });
+        }
+
+        // The slot may have been moved out of the `Resources` its scope was opened on (e.g. by `mem::swap` inside
+        // the closure), so only trust the pointer while that scope is still running on this thread.
+        if !LIVE_SCOPES.with(|live| live.borrow().contains(&slot.scope)) {
+            return Err(EcsError::MissingResource { resource });
+        }
+
+        Ok(slot)
+    }
+
+    pub(crate) fn check_thread(&self, system: &'static str) -> Result<(), EcsError> {
+        let current = thread::current().id();
+        let mut owners = self.non_send.owner.iter().chain(self.scoped.values().map(|slot| &slot.owner));
+
+        match owners.any(|&owner| owner != current) {
+            | true => Err(EcsError::ThreadLocalSystem { system }),
+            | false => Ok(()),
+        }
+    }
+
+    pub fn contains_non_send<T: 'static>(&self) -> bool {
+        self.non_send.resources.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn insert_non_send<T: 'static>(&mut self, resource: T) {
+        self.non_send.check::<T>().unwrap_or_else(|err| panic!("{}", err));
+        self.non_send.owner = Some(thread::current().id());
+        self.non_send
+            .resources
+            .insert(TypeId::of::<T>(), AtomicRefCell::new(Box::new(resource)));
+    }
+
+    pub fn remove_non_send<T: 'static>(&mut self) -> Option<T> {
+        self.non_send.check::<T>().unwrap_or_else(|err| panic!("{}", err));
+
+        let resource = self
+            .non_send
+            .resources
+            .remove(&TypeId::of::<T>())
+            .map(AtomicRefCell::into_inner)
+            .and_then(|v| v.downcast().ok())
+            .map(|v| *v);
+
+        if self.non_send.resources.is_empty() {
+            self.non_send.owner = None;
+        }
+
+        resource
+    }
+
+    pub fn get_non_send<T: 'static>(&self) -> AtomicRef<'_, T> {
+        self.try_get_non_send().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn get_non_send_mut<T: 'static>(&self) -> AtomicRefMut<'_, T> {
+        self.try_get_non_send_mut().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_get_non_send<T: 'static>(&self) -> Result<AtomicRef<'_, T>, EcsError> {
+        let resource = type_name::<T>();
+
+        self.non_send.check::<T>()?;
+
+        let borrow = self
+            .non_send
+            .resources
+            .get(&TypeId::of::<T>())
+            .ok_or(EcsError::MissingResource { resource })?
+            .try_borrow()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(AtomicRef::map(borrow, |v| v.downcast_ref().unwrap()))
+    }
+
+    pub fn try_get_non_send_mut<T: 'static>(&self) -> Result<AtomicRefMut<'_, T>, EcsError> {
+        let resource = type_name::<T>();
+
+        self.non_send.check::<T>()?;
+
+        let borrow = self
+            .non_send
+            .resources
+            .get(&TypeId::of::<T>())
+            .ok_or(EcsError::MissingResource { resource })?
+            .try_borrow_mut()
+            .map_err(|_| EcsError::ResourceBorrowed { resource })?;
+
+        Ok(AtomicRefMut::map(borrow, |v| v.downcast_mut().unwrap()))
+    }
+}
+
+impl NonSendResources {
+    fn check<T>(&self) -> Result<(), EcsError> {
+        match self.owner {
+            | Some(owner) if owner != thread::current().id() => Err(EcsError::NonSendAccess {
+                resource: type_name::<T>(),
+            }),
+            | _ => Ok(()),
+        }
+    }
+}
+
+impl<'resources, K: ScopedResource> ScopedRef<'resources, K> {
+    pub fn with<R, F: for<'a> FnOnce(&K::Target<'a>) -> R>(&self, f: F) -> R {
+        self.resources.with_scoped::<K, R, F>(f)
+    }
+
+    pub fn try_with<R, F: for<'a> FnOnce(&K::Target<'a>) -> R>(&self, f: F) -> Result<R, EcsError> {
+        self.resources.try_with_scoped::<K, R, F>(f)
+    }
+}
+
+impl<'resources, K: ScopedResource> ScopedMut<'resources, K> {
+    pub fn with<R, F: for<'a> FnOnce(&K::Target<'a>) -> R>(&self, f: F) -> R {
+        self.resources.with_scoped::<K, R, F>(f)
+    }
+
+    pub fn with_mut<R, F: for<'a> FnOnce(&mut K::Target<'a>) -> R>(&mut self, f: F) -> R {
+        self.resources.with_scoped_mut::<K, R, F>(f)
+    }
+
+    pub fn try_with<R, F: for<'a> FnOnce(&K::Target<'a>) -> R>(&self, f: F) -> Result<R, EcsError> {
+        self.resources.try_with_scoped::<K, R, F>(f)
+    }
+
+    pub fn try_with_mut<R, F: for<'a> FnOnce(&mut K::Target<'a>) -> R>(&mut self, f: F) -> Result<R, EcsError> {
+        self.resources.try_with_scoped_mut::<K, R, F>(f)
+    }
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        LIVE_SCOPES.with(|live| live.borrow_mut().retain(|&scope| scope != self.scope));
+
+        if self.resources.scoped.get(&self.id).map(|slot| slot.scope) != Some(self.scope) {
+            return;
+        }
+
+        match self.previous.take() {
+            | Some(previous) => self.resources.scoped.insert(self.id, previous),
+            | None => self.resources.scoped.remove(&self.id),
+        };
+    }
+}
+
+impl Drop for NonSendResources {
+    fn drop(&mut self) {
+        if self.check::<Self>().is_err() {
+            std::mem::forget(std::mem::take(&mut self.resources));
+
+            if !thread::panicking() {
+                panic!("non-send resources dropped on a thread other than their owner");
+            }
+        }
+    }
 }
 
 impl<'a> dyn Resource + 'a {
@@ -230,8 +630,45 @@ macro_rules! impl_resource_set {
             unsafe fn fetch_unchecked(resources: &'resources Resources) -> Self::Result {
                 ($($ty::fetch_unchecked(resources),)+)
             }
+
+            fn thread_local() -> bool {
+                $($ty::thread_local())||+
+            }
         }
     };
 }
 
 impl_resource_set!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+
+#[cfg(doctest)]
+/// ```compile_fail,E0521
+/// use ecs::resource::{Resources, ScopedResource};
+///
+/// struct Log;
+///
+/// impl ScopedResource for Log {
+///     type Target<'a> = &'a mut Vec<u32>;
+/// }
+///
+/// fn leak<'r>(resources: &'r Resources) -> &'r Vec<u32> {
+///     let mut leaked = None;
+///
+///     resources.with_scoped::<Log, _, _>(|log| leaked = Some(&**log));
+///     leaked.unwrap()
+/// }
+/// ```
+///
+/// ```
+/// use ecs::resource::{Resources, ScopedResource};
+///
+/// struct Log;
+///
+/// impl ScopedResource for Log {
+///     type Target<'a> = &'a mut Vec<u32>;
+/// }
+///
+/// fn sum(resources: &Resources) -> u32 {
+///     resources.with_scoped::<Log, _, _>(|log| log.iter().sum())
+/// }
+/// ```
+struct ScopedBorrowsCannotEscape;
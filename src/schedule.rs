@@ -1,7 +1,10 @@
+use crate::error::EcsError;
+use crate::query::QueryAccess;
 use crate::resource::{ResourceSet, Resources};
 use crate::system::{QuerySet, System, SystemFn};
 use crate::type_list::{Append, Concat, Flatten, UnFlatten};
 use crate::world::World;
+use std::any::type_name;
 
 pub struct Schedule<S> {
     systems: S,
@@ -13,6 +16,8 @@ pub struct DynSchedule<'system> {
 
 pub trait Systems {
     fn run(&mut self, world: &mut World, resources: &mut Resources);
+    fn check(&self) -> Result<(), EcsError>;
+    fn check_thread(&self, resources: &Resources) -> Result<(), EcsError>;
 }
 
 pub trait SystemBundle {
@@ -29,6 +34,9 @@ pub trait DynSystemBundle<'system>: 'system {
 
 pub trait DynSystem {
     fn run(&mut self, world: &mut World, resources: &mut Resources);
+    fn name(&self) -> &'static str;
+    fn is_thread_local(&self) -> bool;
+    fn access(&self) -> QueryAccess;
 }
 
 impl<T: System> DynSystem for T {
@@ -38,6 +46,18 @@ impl<T: System> DynSystem for T {
 
         System::run(self, queries, resources);
     }
+
+    fn name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn is_thread_local(&self) -> bool {
+        System::is_thread_local(self)
+    }
+
+    fn access(&self) -> QueryAccess {
+        System::access(self)
+    }
 }
 
 impl Schedule<()> {
@@ -52,9 +72,19 @@ impl<S> Schedule<S> {
         S: Append<T>,
         T: System,
     {
-        Schedule {
+        self.try_with_system(system).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_system<T>(self, system: T) -> Result<Schedule<S::Output>, EcsError>
+    where
+        S: Append<T>,
+        T: System,
+    {
+        system.access().check()?;
+
+        Ok(Schedule {
             systems: self.systems.append(system),
-        }
+        })
     }
 
     pub fn with_systems<T>(self, systems: T) -> Schedule<S::Output>
@@ -62,6 +92,8 @@ impl<S> Schedule<S> {
         T: Systems + UnFlatten,
         S: Concat<T::Output>,
     {
+        systems.check().unwrap_or_else(|err| panic!("{}", err));
+
         Schedule {
             systems: self.systems.concat(systems.unflatten()),
         }
@@ -96,7 +128,13 @@ impl<S: Flatten> Schedule<S> {
 
 impl<S: Systems> Schedule<S> {
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        self.try_run(world, resources).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_run(&mut self, world: &mut World, resources: &mut Resources) -> Result<(), EcsError> {
+        self.systems.check_thread(resources)?;
         self.systems.run(world, resources);
+        Ok(())
     }
 }
 
@@ -110,6 +148,11 @@ impl<'system> DynSchedule<'system> {
         self
     }
 
+    pub fn try_with_system<S: System + 'system>(mut self, system: S) -> Result<Self, EcsError> {
+        self.try_add_system(system)?;
+        Ok(self)
+    }
+
     pub fn with_system_fn<F: for<'data> FnMut(&'data mut World, &'data Resources) + 'system>(self, func: F) -> Self {
         self.with_system(SystemFn(func))
     }
@@ -120,7 +163,13 @@ impl<'system> DynSchedule<'system> {
     }
 
     pub fn add_system<S: DynSystem + 'system>(&mut self, system: S) {
+        self.try_add_system(system).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_add_system<S: DynSystem + 'system>(&mut self, system: S) -> Result<(), EcsError> {
+        system.access().check()?;
         self.systems.push(Box::new(system));
+        Ok(())
     }
 
     pub fn add_system_fn<F: for<'data> FnMut(&'data mut World, &'data Resources) + 'system>(&mut self, func: F) {
@@ -132,12 +181,21 @@ impl<'system> DynSchedule<'system> {
     }
 
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
+        self.try_run(world, resources).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_run(&mut self, world: &mut World, resources: &mut Resources) -> Result<(), EcsError> {
+        for system in self.systems.iter().filter(|system| system.is_thread_local()) {
+            resources.check_thread(system.name())?;
+        }
+
         let world = world as *mut World;
         let resources = resources as *mut Resources;
 
         self.systems
             .iter_mut()
             .for_each(move |system| unsafe { system.run(&mut *world, &mut *resources) });
+        Ok(())
     }
 }
 
@@ -165,6 +223,24 @@ macro_rules! impl_systems {
                     );)*
                 }
             }
+
+            #[allow(non_snake_case)]
+            fn check(&self) -> Result<(), EcsError> {
+                let ($($ty,)*) = self;
+
+                $($ty.access().check()?;)*
+                Ok(())
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            fn check_thread(&self, resources: &Resources) -> Result<(), EcsError> {
+                let ($($ty,)*) = self;
+
+                $(if $ty.is_thread_local() {
+                    resources.check_thread(type_name::<$ty>())?;
+                })*
+                Ok(())
+            }
         }
     };
 }
@@ -0,0 +1,267 @@
+use crate::archetype::{Archetype, ArchetypeLayout};
+use crate::component::{ComponentId, ComponentIndex};
+use crate::entity::{Entity, EntityMap};
+use crate::error::EcsError;
+use crate::prefab::CloneFns;
+use crate::relation::RelationIndex;
+use crate::world::World;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct Snapshot {
+    archetypes: Vec<SnapshotArchetype>,
+    entity_counter: u64,
+    relations: RelationIndex,
+}
+
+#[derive(Clone)]
+struct SnapshotArchetype {
+    layout: Arc<ArchetypeLayout>,
+    entities: Arc<Vec<Entity>>,
+    columns: Vec<SnapshotColumn>,
+}
+
+#[derive(Clone)]
+struct SnapshotColumn {
+    id: ComponentId,
+    tick: u64,
+    data: Option<(Arc<dyn Any + Send + Sync>, CloneFns)>,
+}
+
+pub struct SnapshotRing {
+    capacity: usize,
+    snapshots: VecDeque<(u64, Snapshot)>,
+}
+
+impl Snapshot {
+    pub fn len(&self) -> usize {
+        self.archetypes.iter().map(|a| a.entities.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.archetypes.iter().any(|a| a.entities.contains(&entity))
+    }
+
+    pub fn shared_columns(&self, other: &Snapshot) -> usize {
+        self.archetypes
+            .iter()
+            .zip(&other.archetypes)
+            .flat_map(|(a, b)| a.columns.iter().zip(&b.columns))
+            .filter(|(a, b)| match (&a.data, &b.data) {
+                | (Some((a, _)), Some((b, _))) => Arc::ptr_eq(a, b),
+                | _ => false,
+            })
+            .count()
+    }
+
+    fn capture(world: &World, previous: Option<&Snapshot>) -> Result<Self, EcsError> {
+        let mut archetypes = Vec::with_capacity(world.archetypes.len());
+
+        for archetype in &world.archetypes {
+            let previous = previous
+                .and_then(|p| p.archetypes.get(archetype.index.0 as usize))
+                .filter(|p| p.layout == archetype.layout);
+            let mut columns = Vec::with_capacity(archetype.layout.components.len());
+
+            for &id in &archetype.layout.components {
+                let shared = previous.and_then(|p| {
+                    let tick = world.components.get_any(id)?.change_tick(archetype.index)?;
+
+                    p.columns.iter().find(|c| c.id == id && c.tick == tick)
+                });
+
+                columns.push(match shared {
+                    | Some(column) => column.clone(),
+                    | None => SnapshotColumn::capture(world, archetype, id)?,
+                });
+            }
+
+            let entities = match previous {
+                | Some(p) if *p.entities == archetype.entities => p.entities.clone(),
+                | _ => Arc::new(archetype.entities.clone()),
+            };
+
+            archetypes.push(SnapshotArchetype {
+                layout: archetype.layout.clone(),
+                entities,
+                columns,
+            });
+        }
+
+        Ok(Self {
+            archetypes,
+            entity_counter: world.entity_counter.load(Ordering::Relaxed),
+            relations: world.relations.clone(),
+        })
+    }
+}
+
+impl SnapshotColumn {
+    fn capture(world: &World, archetype: &Archetype, id: ComponentId) -> Result<Self, EcsError> {
+        let storage = world.components.get_any(id).unwrap();
+        let tick = storage.change_tick(archetype.index).unwrap();
+
+        if archetype.entities.is_empty() {
+            return Ok(Self { id, tick, data: None });
+        }
+
+        let not_cloneable = || EcsError::NotCloneable {
+            component: storage.component_name().to_owned(),
+        };
+        let fns = world
+            .registry()
+            .get(id)
+            .and_then(|info| info.clone_fns())
+            .ok_or_else(not_cloneable)?;
+        let data = (fns.column)(storage, archetype.index).ok_or_else(not_cloneable)?;
+
+        Ok(Self {
+            id,
+            tick,
+            data: Some((Arc::from(data), fns)),
+        })
+    }
+}
+
+impl World {
+    pub fn snapshot(&mut self) -> Result<Snapshot, EcsError> {
+        let snapshot = Snapshot::capture(self, self.snapshot.as_ref())?;
+
+        self.snapshot = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        assert!(
+            snapshot.archetypes.len() <= self.archetypes.len(),
+            "snapshot was not taken from this world"
+        );
+
+        let mut restored = snapshot.clone();
+
+        for archetype in &mut self.archetypes {
+            let saved = snapshot.archetypes.get(archetype.index.0 as usize);
+
+            if let Some(saved) = saved {
+                assert!(saved.layout == archetype.layout, "snapshot was not taken from this world");
+            }
+
+            for &id in &archetype.layout.components {
+                let storage = self.components.get_any_mut(id).unwrap();
+
+                let column = restored
+                    .archetypes
+                    .get_mut(archetype.index.0 as usize)
+                    .and_then(|s| s.columns.iter_mut().find(|c| c.id == id));
+
+                match column {
+                    | Some(column) if storage.change_tick(archetype.index) == Some(column.tick) => {},
+                    | Some(column) => {
+                        match &column.data {
+                            | Some((data, fns)) => (fns.restore)(storage, archetype.index, &**data),
+                            | None => storage.clear(archetype.index),
+                        }
+
+                        storage.mark_all_changed(archetype.index);
+                        column.tick = storage.change_tick(archetype.index).unwrap();
+                    },
+                    | None => storage.clear(archetype.index),
+                }
+            }
+
+            archetype.entities = saved.map_or_else(Vec::new, |s| Vec::clone(&s.entities));
+        }
+
+        self.entities = EntityMap::default();
+
+        for archetype in &self.archetypes {
+            self.entities
+                .insert(&archetype.entities, archetype.index, ComponentIndex(0));
+        }
+
+        self.entity_counter.store(snapshot.entity_counter, Ordering::Relaxed);
+        self.relations = snapshot.relations.clone();
+        self.snapshot = Some(restored);
+        self.indexes.invalidate();
+    }
+}
+
+impl SnapshotRing {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "snapshot ring must hold at least one snapshot");
+
+        Self {
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn push(&mut self, tick: u64, snapshot: Snapshot) {
+        while self.snapshots.back().is_some_and(|&(t, _)| t >= tick) {
+            self.snapshots.pop_back();
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        self.snapshots.push_back((tick, snapshot));
+    }
+
+    pub fn record(&mut self, tick: u64, world: &mut World) -> Result<(), EcsError> {
+        let snapshot = world.snapshot()?;
+
+        self.push(tick, snapshot);
+        Ok(())
+    }
+
+    pub fn get(&self, tick: u64) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|&&(t, _)| t == tick).map(|(_, s)| s)
+    }
+
+    pub fn latest(&self) -> Option<(u64, &Snapshot)> {
+        self.snapshots.back().map(|(t, s)| (*t, s))
+    }
+
+    pub fn oldest(&self) -> Option<(u64, &Snapshot)> {
+        self.snapshots.front().map(|(t, s)| (*t, s))
+    }
+
+    pub fn ticks(&self) -> impl Iterator<Item = u64> + '_ {
+        self.snapshots.iter().map(|&(t, _)| t)
+    }
+
+    pub fn rollback(&mut self, tick: u64, world: &mut World) -> bool {
+        let index = match self.snapshots.iter().position(|&(t, _)| t == tick) {
+            | Some(index) => index,
+            | None => return false,
+        };
+
+        self.snapshots.truncate(index + 1);
+        world.restore(&self.snapshots[index].1);
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
@@ -1,24 +1,38 @@
+mod blob;
 mod null;
 mod single;
+mod ticks;
 mod vec;
 
+pub use blob::{BlobArchetypeStorage, BlobStorage};
 pub use null::NullStorage;
 pub use single::SingleStorage;
+pub use ticks::{change_tick, ChangeTicks};
 pub use vec::VecStorage;
 
 use crate::archetype::ArchetypeIndex;
-use crate::component::{Component, ComponentIndex};
-use std::any::{Any, TypeId};
+use crate::component::{Component, ComponentId, ComponentIndex};
+use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
+use std::ops::Range;
+use std::ptr::NonNull;
 
 pub trait AnyStorage {
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` valid, initialized values of the stored component type. The values are moved into
+    /// the storage and must not be used or dropped by the caller afterwards.
     unsafe fn extend_memcpy(&mut self, ptr: *const u8, len: usize);
     fn swap_remove(&mut self, component: ComponentIndex);
+
+    fn is_full(&self) -> bool {
+        false
+    }
 }
 
 pub trait Storage<'a, T: Component>: AnyStorage + Default {
-    type Iter: Iterator<Item = &'a T>;
-    type IterMut: Iterator<Item = &'a mut T>;
+    type Iter: DoubleEndedIterator<Item = &'a T> + ExactSizeIterator;
+    type IterMut: DoubleEndedIterator<Item = &'a mut T> + ExactSizeIterator;
 
     fn get(&'a self, component: ComponentIndex) -> Option<&'a T>;
     fn get_mut(&'a mut self, component: ComponentIndex) -> Option<&'a mut T>;
@@ -27,22 +41,71 @@ pub trait Storage<'a, T: Component>: AnyStorage + Default {
 
     fn iter(&'a self) -> Self::Iter;
     fn iter_mut(&'a mut self) -> Self::IterMut;
+    fn iter_range(&'a self, rows: Range<usize>) -> Self::Iter;
+    fn iter_mut_range(&'a mut self, rows: Range<usize>) -> Self::IterMut;
+    /// # Safety
+    ///
+    /// `this` must point to a live storage that is not otherwise borrowed mutably, and no other reference to the
+    /// components in `rows` may be alive while the iterator is. Disjoint ranges of the same storage may be iterated
+    /// concurrently.
+    unsafe fn iter_mut_rows(this: NonNull<Self>, rows: Range<usize>) -> Self::IterMut;
+    fn as_slice(&'a self) -> &'a [T];
+    fn as_mut_slice(&'a mut self) -> &'a mut [T];
 }
 
 pub struct ArchetypeStorage<T: Component> {
     index: Vec<usize>,
     data: Vec<T::Storage>,
+    ticks: Vec<ChangeTicks>,
 }
 
-pub trait AnyArchetypeStorage: Any {
+pub trait AnyArchetypeStorage: Any + Send + Sync {
+    fn component_name(&self) -> &str;
     fn register_archetype(&mut self, archetype: ArchetypeIndex);
+    /// # Safety
+    ///
+    /// See [`AnyStorage::extend_memcpy`].
     unsafe fn extend_memcpy(&mut self, archetype: ArchetypeIndex, ptr: *const u8, len: usize);
     fn swap_remove(&mut self, archetype: ArchetypeIndex, component: ComponentIndex);
+    fn move_component(&mut self, from: ArchetypeIndex, component: ComponentIndex, to: ArchetypeIndex);
+    fn move_to(&mut self, archetype: ArchetypeIndex, component: ComponentIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex);
+    fn move_column(&mut self, archetype: ArchetypeIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex);
+    fn get_ptr(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<NonNull<u8>>;
+    fn get_any(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&dyn Any>;
+    fn get_bytes(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&[u8]>;
+    fn get_any_mut(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&mut dyn Any>;
+    fn get_any_ptr(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<NonNull<dyn Any>>;
+    fn clear(&mut self, archetype: ArchetypeIndex);
+    fn change_ticks(&self, archetype: ArchetypeIndex) -> Option<&ChangeTicks>;
+
+    fn is_full(&self, _archetype: ArchetypeIndex) -> bool {
+        false
+    }
+
+    fn change_tick(&self, archetype: ArchetypeIndex) -> Option<u64> {
+        self.change_ticks(archetype).map(|ticks| ticks.column())
+    }
+
+    fn component_tick(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<u64> {
+        self.change_ticks(archetype)?.row(component)
+    }
+
+    fn mark_all_changed(&self, archetype: ArchetypeIndex) {
+        if let Some(ticks) = self.change_ticks(archetype) {
+            ticks.mark_all();
+        }
+    }
+
+    fn mark_changed(&self, archetype: ArchetypeIndex, component: ComponentIndex) {
+        if let Some(ticks) = self.change_ticks(archetype) {
+            ticks.mark(component);
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct Components {
-    storages: HashMap<TypeId, Box<dyn AnyArchetypeStorage>>,
+    storages: HashMap<ComponentId, Box<dyn AnyArchetypeStorage>>,
 }
 
 impl<T: Component> Default for ArchetypeStorage<T> {
@@ -50,6 +113,7 @@ impl<T: Component> Default for ArchetypeStorage<T> {
         Self {
             index: Vec::new(),
             data: Vec::new(),
+            ticks: Vec::new(),
         }
     }
 }
@@ -66,25 +130,56 @@ impl<T: Component> ArchetypeStorage<T> {
     }
 
     pub fn get_mut(&mut self, archetype: ArchetypeIndex) -> Option<&mut T::Storage> {
-        self.index.get(archetype.0 as usize).copied().and_then(move |index| {
-            self.data.get_mut(index)
-        })
+        unsafe { self.get_mut_unchecked(archetype) }
     }
 
+    /// # Safety
+    ///
+    /// The caller must ensure no other reference to this archetype's column is alive while the returned one is.
     pub unsafe fn get_mut_unchecked(&self, archetype: ArchetypeIndex) -> Option<&mut T::Storage> {
-        self.index.get(archetype.0 as usize).and_then(|&index| {
-            std::mem::transmute(self.data.get(index))
+        self.get_mut_tracked(archetype).map(|(storage, ticks)| {
+            ticks.mark_all();
+            &mut *storage.as_ptr()
         })
     }
 
+    pub(crate) fn get_mut_tracked(&self, archetype: ArchetypeIndex) -> Option<(NonNull<T::Storage>, &ChangeTicks)> {
+        let index = *self.index.get(archetype.0 as usize)?;
+        let ticks = self.ticks.get(index)?;
+
+        NonNull::new(self.data.as_ptr().wrapping_add(index) as *mut T::Storage).map(|storage| (storage, ticks))
+    }
+
+    pub fn get_component_mut(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&mut T> {
+        let index = *self.index.get(archetype.0 as usize)?;
+
+        self.ticks.get(index)?.mark(component);
+        self.data.get_mut(index)?.get_mut(component)
+    }
+
     pub fn extend<I: IntoIterator<Item = T>>(&mut self, archetype: ArchetypeIndex, items: I) {
         if let Some(&index) = self.index.get(archetype.0 as usize) {
-            self.data[index].extend(items);
+            let mut len = 0;
+
+            self.data[index].extend(items.into_iter().inspect(|_| len += 1));
+            self.ticks[index].push(len);
         }
     }
+
+    pub fn remove(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<T> {
+        let index = *self.index.get(archetype.0 as usize)?;
+        let value = self.data.get_mut(index)?.remove(component)?;
+
+        self.ticks[index].swap_remove(component);
+        Some(value)
+    }
 }
 
 impl<T: Component> AnyArchetypeStorage for ArchetypeStorage<T> {
+    fn component_name(&self) -> &str {
+        type_name::<T>()
+    }
+
     fn register_archetype(&mut self, archetype: ArchetypeIndex) {
         let index = archetype.0 as usize;
 
@@ -94,21 +189,99 @@ impl<T: Component> AnyArchetypeStorage for ArchetypeStorage<T> {
 
         self.index[index] = self.data.len();
         self.data.push(T::Storage::default());
+        self.ticks.push(ChangeTicks::default());
     }
 
     unsafe fn extend_memcpy(&mut self, archetype: ArchetypeIndex, ptr: *const u8, len: usize) {
         let index = self.index[archetype.0 as usize];
         self.data[index].extend_memcpy(ptr, len);
+        self.ticks[index].push(len);
     }
 
     fn swap_remove(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) {
-        let index = self.index[archetype.0 as usize];
-        self.data[index].remove(component).unwrap();
+        self.remove(archetype, component).unwrap();
+    }
+
+    fn move_component(&mut self, from: ArchetypeIndex, component: ComponentIndex, to: ArchetypeIndex) {
+        let from = self.index[from.0 as usize];
+        let to = self.index[to.0 as usize];
+        let value = self.data[from].remove(component).unwrap();
+        let tick = self.ticks[from].swap_remove(component);
+
+        self.data[to].extend(std::iter::once(value));
+        self.ticks[to].push_tick(tick);
+    }
+
+    fn move_to(&mut self, archetype: ArchetypeIndex, component: ComponentIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex) {
+        let value = self.remove(archetype, component).unwrap();
+
+        other.downcast_mut::<T>().unwrap().extend(to, std::iter::once(value));
+    }
+
+    fn move_column(&mut self, archetype: ArchetypeIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex) {
+        let other = other.downcast_mut::<T>().unwrap();
+        let from = self.index[archetype.0 as usize];
+        let dest = other.index[to.0 as usize];
+        let len = self.ticks[from].len();
+
+        if other.ticks[dest].is_empty() {
+            std::mem::swap(&mut self.data[from], &mut other.data[dest]);
+        } else {
+            let mut values = (0..len)
+                .rev()
+                .map(|i| self.data[from].remove(ComponentIndex(i as u32)).unwrap())
+                .collect::<Vec<_>>();
+
+            values.reverse();
+            other.data[dest].extend(values);
+        }
+
+        self.ticks[from].clear();
+        other.ticks[dest].push(len);
+    }
+
+    fn get_ptr(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<NonNull<u8>> {
+        self.get(archetype)
+            .and_then(|s| s.get(component))
+            .map(|c| NonNull::from(c).cast())
+    }
+
+    fn get_any(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&dyn Any> {
+        self.get(archetype)
+            .and_then(|s| s.get(component))
+            .map(|c| c as &dyn Any)
+    }
+
+    fn get_bytes(&self, _: ArchetypeIndex, _: ComponentIndex) -> Option<&[u8]> {
+        None
+    }
+
+    fn get_any_mut(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&mut dyn Any> {
+        self.get_component_mut(archetype, component).map(|c| c as &mut dyn Any)
+    }
+
+    fn get_any_ptr(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<NonNull<dyn Any>> {
+        self.get_any(archetype, component).map(NonNull::from)
+    }
+
+    fn is_full(&self, archetype: ArchetypeIndex) -> bool {
+        self.get(archetype).is_some_and(|s| s.is_full())
+    }
+
+    fn clear(&mut self, archetype: ArchetypeIndex) {
+        if let Some(&index) = self.index.get(archetype.0 as usize) {
+            self.data[index] = T::Storage::default();
+            self.ticks[index].clear();
+        }
+    }
+
+    fn change_ticks(&self, archetype: ArchetypeIndex) -> Option<&ChangeTicks> {
+        self.index.get(archetype.0 as usize).and_then(|&index| self.ticks.get(index))
     }
 }
 
 impl Components {
-    pub fn get_or_insert<F>(&mut self, ty: TypeId, ctor: F) -> &mut dyn AnyArchetypeStorage
+    pub fn get_or_insert<F>(&mut self, ty: ComponentId, ctor: F) -> &mut dyn AnyArchetypeStorage
     where
         F: FnOnce() -> Box<dyn AnyArchetypeStorage>,
     {
@@ -117,21 +290,21 @@ impl Components {
 
     pub fn get<T: Component>(&self) -> Option<&ArchetypeStorage<T>> {
         self.storages
-            .get(&TypeId::of::<T>())
+            .get(&ComponentId::of::<T>())
             .and_then(|s| s.downcast_ref::<T>())
     }
 
     pub fn get_mut<T: Component>(&mut self) -> Option<&mut ArchetypeStorage<T>> {
         self.storages
-            .get_mut(&TypeId::of::<T>())
+            .get_mut(&ComponentId::of::<T>())
             .and_then(|s| s.downcast_mut::<T>())
     }
 
-    pub fn get_any(&self, ty: TypeId) -> Option<&dyn AnyArchetypeStorage> {
+    pub fn get_any(&self, ty: ComponentId) -> Option<&dyn AnyArchetypeStorage> {
         self.storages.get(&ty).map(|s| &**s)
     }
 
-    pub fn get_any_mut(&mut self, ty: TypeId) -> Option<&mut dyn AnyArchetypeStorage> {
+    pub fn get_any_mut(&mut self, ty: ComponentId) -> Option<&mut dyn AnyArchetypeStorage> {
         self.storages.get_mut(&ty).map(|s| &mut **s)
     }
 }
@@ -157,4 +330,25 @@ impl dyn AnyArchetypeStorage {
             None
         }
     }
+
+    #[inline]
+    pub fn is_blob(&self) -> bool {
+        self.type_id() == TypeId::of::<BlobArchetypeStorage>()
+    }
+
+    pub fn as_blob(&self) -> Option<&BlobArchetypeStorage> {
+        if self.is_blob() {
+            Some(unsafe { &*(self as *const _ as *const BlobArchetypeStorage) })
+        } else {
+            None
+        }
+    }
+
+    pub fn as_blob_mut(&mut self) -> Option<&mut BlobArchetypeStorage> {
+        if self.is_blob() {
+            Some(unsafe { &mut *(self as *mut _ as *mut BlobArchetypeStorage) })
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,344 @@
+use super::{AnyArchetypeStorage, AnyStorage, ChangeTicks};
+use crate::archetype::ArchetypeIndex;
+use crate::component::{ComponentDescriptor, ComponentIndex};
+use std::alloc::{self, Layout};
+use std::any::Any;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+pub struct BlobStorage {
+    descriptor: Arc<ComponentDescriptor>,
+    data: NonNull<u8>,
+    len: usize,
+    capacity: usize,
+}
+
+pub struct BlobArchetypeStorage {
+    descriptor: Arc<ComponentDescriptor>,
+    index: Vec<usize>,
+    data: Vec<BlobStorage>,
+    ticks: Vec<ChangeTicks>,
+}
+
+impl BlobStorage {
+    pub fn new(descriptor: Arc<ComponentDescriptor>) -> Self {
+        let layout = descriptor.layout();
+
+        Self {
+            data: NonNull::new(layout.align() as *mut u8).unwrap(),
+            capacity: if layout.size() == 0 { usize::MAX } else { 0 },
+            len: 0,
+            descriptor,
+        }
+    }
+
+    pub fn descriptor(&self) -> &ComponentDescriptor {
+        &self.descriptor
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, component: ComponentIndex) -> Option<NonNull<u8>> {
+        let index = component.0 as usize;
+
+        if index < self.len {
+            Some(unsafe { NonNull::new_unchecked(self.data.as_ptr().add(index * self.stride())) })
+        } else {
+            None
+        }
+    }
+
+    pub fn bytes(&self, component: ComponentIndex) -> Option<&[u8]> {
+        let size = self.descriptor.layout().size();
+
+        self.get(component)
+            .map(|ptr| unsafe { std::slice::from_raw_parts(ptr.as_ptr(), size) })
+    }
+
+    /// # Safety
+    ///
+    /// If this column's descriptor has a drop fn, anything written through the returned slice must leave a valid value
+    /// of the component type behind; the drop fn will run on those bytes.
+    pub unsafe fn bytes_mut(&mut self, component: ComponentIndex) -> Option<&mut [u8]> {
+        let size = self.descriptor.layout().size();
+
+        self.get(component)
+            .map(|ptr| unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), size) })
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid, initialized value of this column's component type. The value is moved into the
+    /// column and must not be used or dropped by the caller afterwards.
+    pub unsafe fn replace(&mut self, component: ComponentIndex, ptr: *const u8) {
+        let dst = self.get(component).unwrap().as_ptr();
+
+        if let Some(drop) = self.descriptor.drop_fn() {
+            drop(dst);
+        }
+
+        std::ptr::copy_nonoverlapping(ptr, dst, self.descriptor.layout().size());
+    }
+
+    pub(crate) fn forget(&mut self) {
+        self.len = 0;
+    }
+
+    pub(crate) fn swap_remove_forget(&mut self, component: ComponentIndex) {
+        let index = component.0 as usize;
+        let last = self.len - 1;
+
+        assert!(index < self.len);
+
+        if index != last {
+            unsafe {
+                let stride = self.stride();
+                let base = self.data.as_ptr();
+
+                std::ptr::copy_nonoverlapping(base.add(last * stride), base.add(index * stride), stride);
+            }
+        }
+
+        self.len = last;
+    }
+
+    fn stride(&self) -> usize {
+        self.descriptor.layout().pad_to_align().size()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        let required = self.len + additional;
+
+        if required <= self.capacity {
+            return;
+        }
+
+        let capacity = required.max(self.capacity * 2).max(4);
+        let align = self.descriptor.layout().align();
+        let new_layout = Layout::from_size_align(self.stride() * capacity, align).unwrap();
+
+        let data = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(new_layout)
+            } else {
+                let old_layout = Layout::from_size_align(self.stride() * self.capacity, align).unwrap();
+                alloc::realloc(self.data.as_ptr(), old_layout, new_layout.size())
+            }
+        };
+
+        self.data = NonNull::new(data).unwrap_or_else(|| alloc::handle_alloc_error(new_layout));
+        self.capacity = capacity;
+    }
+}
+
+unsafe impl Send for BlobStorage {
+}
+
+unsafe impl Sync for BlobStorage {
+}
+
+impl AnyStorage for BlobStorage {
+    unsafe fn extend_memcpy(&mut self, ptr: *const u8, len: usize) {
+        self.reserve(len);
+
+        let stride = self.stride();
+        let size = self.descriptor.layout().size();
+        let dst = self.data.as_ptr().add(self.len * stride);
+
+        for i in 0..len {
+            std::ptr::copy_nonoverlapping(ptr.add(i * stride), dst.add(i * stride), size);
+        }
+
+        self.len += len;
+    }
+
+    fn swap_remove(&mut self, component: ComponentIndex) {
+        if let (Some(drop), Some(ptr)) = (self.descriptor.drop_fn(), self.get(component)) {
+            unsafe { drop(ptr.as_ptr()) };
+        }
+
+        self.swap_remove_forget(component);
+    }
+}
+
+impl Drop for BlobStorage {
+    fn drop(&mut self) {
+        if let Some(drop) = self.descriptor.drop_fn() {
+            for i in 0..self.len {
+                unsafe { drop(self.data.as_ptr().add(i * self.stride())) };
+            }
+        }
+
+        if self.capacity != 0 && self.descriptor.layout().size() != 0 {
+            let layout = Layout::from_size_align(self.stride() * self.capacity, self.descriptor.layout().align());
+
+            unsafe { alloc::dealloc(self.data.as_ptr(), layout.unwrap()) };
+        }
+    }
+}
+
+impl BlobArchetypeStorage {
+    pub fn new(descriptor: Arc<ComponentDescriptor>) -> Self {
+        Self {
+            descriptor,
+            index: Vec::new(),
+            data: Vec::new(),
+            ticks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn unregistered() -> Box<dyn AnyArchetypeStorage> {
+        panic!("dynamic components must be registered with the world before use")
+    }
+
+    pub fn descriptor(&self) -> &ComponentDescriptor {
+        &self.descriptor
+    }
+
+    pub(crate) fn shared_descriptor(&self) -> Arc<ComponentDescriptor> {
+        self.descriptor.clone()
+    }
+
+    pub fn get(&self, archetype: ArchetypeIndex) -> Option<&BlobStorage> {
+        self.index
+            .get(archetype.0 as usize)
+            .and_then(|&index| self.data.get(index))
+    }
+
+    pub fn get_mut(&mut self, archetype: ArchetypeIndex) -> Option<&mut BlobStorage> {
+        let index = *self.index.get(archetype.0 as usize)?;
+
+        self.ticks.get(index)?.mark_all();
+        self.data.get_mut(index)
+    }
+
+    /// # Safety
+    ///
+    /// See [`BlobStorage::bytes_mut`].
+    pub unsafe fn bytes_mut(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&mut [u8]> {
+        let index = *self.index.get(archetype.0 as usize)?;
+
+        self.ticks.get(index)?.mark(component);
+        self.data.get_mut(index)?.bytes_mut(component)
+    }
+
+    /// # Safety
+    ///
+    /// Same as [`BlobStorage::replace`].
+    pub unsafe fn replace(&mut self, archetype: ArchetypeIndex, component: ComponentIndex, ptr: *const u8) {
+        let index = self.index[archetype.0 as usize];
+
+        self.ticks[index].mark(component);
+        self.data[index].replace(component, ptr);
+    }
+}
+
+impl AnyArchetypeStorage for BlobArchetypeStorage {
+    fn component_name(&self) -> &str {
+        self.descriptor.name()
+    }
+
+    fn register_archetype(&mut self, archetype: ArchetypeIndex) {
+        let index = archetype.0 as usize;
+
+        if index >= self.index.len() {
+            self.index.resize(index + 1, !0);
+        }
+
+        self.index[index] = self.data.len();
+        self.data.push(BlobStorage::new(self.descriptor.clone()));
+        self.ticks.push(ChangeTicks::default());
+    }
+
+    unsafe fn extend_memcpy(&mut self, archetype: ArchetypeIndex, ptr: *const u8, len: usize) {
+        let index = self.index[archetype.0 as usize];
+        self.data[index].extend_memcpy(ptr, len);
+        self.ticks[index].push(len);
+    }
+
+    fn swap_remove(&mut self, archetype: ArchetypeIndex, component: ComponentIndex) {
+        let index = self.index[archetype.0 as usize];
+        self.data[index].swap_remove(component);
+        self.ticks[index].swap_remove(component);
+    }
+
+    fn move_component(&mut self, from: ArchetypeIndex, component: ComponentIndex, to: ArchetypeIndex) {
+        let from = self.index[from.0 as usize];
+        let to = self.index[to.0 as usize];
+        let ptr = self.data[from].get(component).unwrap();
+
+        unsafe { self.data[to].extend_memcpy(ptr.as_ptr(), 1) };
+        self.data[from].swap_remove_forget(component);
+
+        let tick = self.ticks[from].swap_remove(component);
+        self.ticks[to].push_tick(tick);
+    }
+
+    fn move_to(&mut self, archetype: ArchetypeIndex, component: ComponentIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex) {
+        let from = self.index[archetype.0 as usize];
+        let ptr = self.data[from].get(component).unwrap();
+
+        unsafe { other.extend_memcpy(to, ptr.as_ptr(), 1) };
+        self.data[from].swap_remove_forget(component);
+        self.ticks[from].swap_remove(component);
+    }
+
+    fn move_column(&mut self, archetype: ArchetypeIndex, other: &mut dyn AnyArchetypeStorage, to: ArchetypeIndex) {
+        let other = other.as_blob_mut().unwrap();
+        let from = self.index[archetype.0 as usize];
+        let dest = other.index[to.0 as usize];
+        let len = self.data[from].len();
+
+        if other.data[dest].is_empty() {
+            std::mem::swap(&mut self.data[from], &mut other.data[dest]);
+        } else {
+            for i in 0..len {
+                let ptr = self.data[from].get(ComponentIndex(i as u32)).unwrap();
+
+                unsafe { other.data[dest].extend_memcpy(ptr.as_ptr(), 1) };
+            }
+
+            self.data[from].forget();
+        }
+
+        self.ticks[from].clear();
+        other.ticks[dest].push(len);
+    }
+
+    fn get_ptr(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<NonNull<u8>> {
+        self.get(archetype).and_then(|s| s.get(component))
+    }
+
+    fn get_any(&self, _: ArchetypeIndex, _: ComponentIndex) -> Option<&dyn Any> {
+        None
+    }
+
+    fn get_bytes(&self, archetype: ArchetypeIndex, component: ComponentIndex) -> Option<&[u8]> {
+        self.get(archetype).and_then(|s| s.bytes(component))
+    }
+
+    fn get_any_mut(&mut self, _: ArchetypeIndex, _: ComponentIndex) -> Option<&mut dyn Any> {
+        None
+    }
+
+    fn get_any_ptr(&self, _: ArchetypeIndex, _: ComponentIndex) -> Option<NonNull<dyn Any>> {
+        None
+    }
+
+    fn clear(&mut self, archetype: ArchetypeIndex) {
+        if let Some(&index) = self.index.get(archetype.0 as usize) {
+            self.data[index] = BlobStorage::new(self.descriptor.clone());
+            self.ticks[index].clear();
+        }
+    }
+
+    fn change_ticks(&self, archetype: ArchetypeIndex) -> Option<&ChangeTicks> {
+        self.index.get(archetype.0 as usize).and_then(|&index| self.ticks.get(index))
+    }
+}
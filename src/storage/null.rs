@@ -1,5 +1,6 @@
 use super::*;
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::ptr::NonNull;
 
 pub struct NullStorage<T> {
@@ -89,6 +90,41 @@ impl<'a, T: Component> Storage<'a, T> for NullStorage<T> {
             _marker: PhantomData,
         }
     }
+
+    fn iter_range(&self, rows: Range<usize>) -> Self::Iter {
+        assert!(rows.end <= self.len);
+
+        NullIter {
+            len: rows.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn iter_mut_range(&mut self, rows: Range<usize>) -> Self::IterMut {
+        assert!(rows.end <= self.len);
+
+        NullIterMut {
+            len: rows.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    unsafe fn iter_mut_rows(this: NonNull<Self>, rows: Range<usize>) -> Self::IterMut {
+        assert!(rows.end <= (*this.as_ptr()).len);
+
+        NullIterMut {
+            len: rows.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &'a [T] {
+        unsafe { std::slice::from_raw_parts(NonNull::dangling().as_ptr(), self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &'a mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(NonNull::dangling().as_ptr(), self.len) }
+    }
 }
 
 impl<'a, T> Iterator for NullIter<'a, T> {
@@ -102,6 +138,10 @@ impl<'a, T> Iterator for NullIter<'a, T> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
 impl<'a, T> Iterator for NullIterMut<'a, T> {
@@ -115,4 +155,26 @@ impl<'a, T> Iterator for NullIterMut<'a, T> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for NullIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for NullIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for NullIter<'a, T> {
+}
+
+impl<'a, T> ExactSizeIterator for NullIterMut<'a, T> {
 }
@@ -1,6 +1,8 @@
 use super::{AnyStorage, Storage};
 use crate::component::{Component, ComponentIndex};
 use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::ptr::NonNull;
 
 pub struct SingleStorage<T> {
     value: Option<T>,
@@ -30,6 +32,10 @@ impl<T> AnyStorage for SingleStorage<T> {
         assert_eq!(component.0, 0);
         self.value = None;
     }
+
+    fn is_full(&self) -> bool {
+        self.value.is_some()
+    }
 }
 
 impl<'a, T: Component> Storage<'a, T> for SingleStorage<T> {
@@ -68,4 +74,37 @@ impl<'a, T: Component> Storage<'a, T> for SingleStorage<T> {
     fn iter_mut(&'a mut self) -> Self::IterMut {
         self.value.iter_mut()
     }
+
+    fn iter_range(&'a self, rows: Range<usize>) -> Self::Iter {
+        let mut iter = self.value.iter();
+
+        if rows.start > 0 || rows.end == 0 {
+            iter.next();
+        }
+
+        iter
+    }
+
+    fn iter_mut_range(&'a mut self, rows: Range<usize>) -> Self::IterMut {
+        let mut iter = self.value.iter_mut();
+
+        if rows.start > 0 || rows.end == 0 {
+            iter.next();
+        }
+
+        iter
+    }
+
+    unsafe fn iter_mut_rows(this: NonNull<Self>, rows: Range<usize>) -> Self::IterMut {
+        // A single storage has one row, so only one non-empty range of it can exist.
+        (*this.as_ptr()).iter_mut_range(rows)
+    }
+
+    fn as_slice(&'a self) -> &'a [T] {
+        self.value.as_slice()
+    }
+
+    fn as_mut_slice(&'a mut self) -> &'a mut [T] {
+        self.value.as_mut_slice()
+    }
 }
@@ -0,0 +1,98 @@
+use crate::component::ComponentIndex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static CHANGE_TICK: AtomicU64 = AtomicU64::new(1);
+
+pub struct ChangeTicks {
+    column: AtomicU64,
+    rows: Vec<AtomicU64>,
+}
+
+pub fn change_tick() -> u64 {
+    CHANGE_TICK.load(Ordering::Relaxed)
+}
+
+fn next_change_tick() -> u64 {
+    CHANGE_TICK.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+impl Default for ChangeTicks {
+    fn default() -> Self {
+        Self {
+            column: AtomicU64::new(next_change_tick()),
+            rows: Vec::new(),
+        }
+    }
+}
+
+impl ChangeTicks {
+    pub fn column(&self) -> u64 {
+        self.column.load(Ordering::Relaxed)
+    }
+
+    pub fn row(&self, component: ComponentIndex) -> Option<u64> {
+        self.rows
+            .get(component.0 as usize)
+            .map(|tick| tick.load(Ordering::Relaxed))
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub(crate) fn rows(&self) -> &[AtomicU64] {
+        &self.rows
+    }
+
+    pub(crate) fn touch(&self) -> u64 {
+        let tick = next_change_tick();
+
+        self.column.store(tick, Ordering::Relaxed);
+        tick
+    }
+
+    pub(crate) fn mark(&self, component: ComponentIndex) {
+        let tick = self.touch();
+
+        if let Some(row) = self.rows.get(component.0 as usize) {
+            row.store(tick, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn mark_all(&self) {
+        self.set(self.touch());
+    }
+
+    pub(crate) fn set(&self, tick: u64) {
+        self.column.store(tick, Ordering::Relaxed);
+
+        for row in &self.rows {
+            row.store(tick, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn push(&mut self, len: usize) {
+        let tick = self.touch();
+
+        self.rows.extend((0..len).map(|_| AtomicU64::new(tick)));
+    }
+
+    pub(crate) fn push_tick(&mut self, tick: u64) {
+        self.touch();
+        self.rows.push(AtomicU64::new(tick));
+    }
+
+    pub(crate) fn swap_remove(&mut self, component: ComponentIndex) -> u64 {
+        self.touch();
+        self.rows.swap_remove(component.0 as usize).into_inner()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.touch();
+        self.rows.clear();
+    }
+}
@@ -1,5 +1,7 @@
 use super::{AnyStorage, Storage};
 use crate::component::{Component, ComponentIndex};
+use std::ops::Range;
+use std::ptr::NonNull;
 
 pub struct VecStorage<T> {
     vec: Vec<T>,
@@ -60,4 +62,27 @@ impl<'a, T: Component> Storage<'a, T> for VecStorage<T> {
     fn iter_mut(&'a mut self) -> Self::IterMut {
         self.vec.iter_mut()
     }
+
+    fn iter_range(&'a self, rows: Range<usize>) -> Self::Iter {
+        self.vec[rows].iter()
+    }
+
+    fn iter_mut_range(&'a mut self, rows: Range<usize>) -> Self::IterMut {
+        self.vec[rows].iter_mut()
+    }
+
+    unsafe fn iter_mut_rows(this: NonNull<Self>, rows: Range<usize>) -> Self::IterMut {
+        let vec = &(*this.as_ptr()).vec;
+
+        assert!(rows.start <= rows.end && rows.end <= vec.len());
+        std::slice::from_raw_parts_mut((vec.as_ptr() as *mut T).add(rows.start), rows.len()).iter_mut()
+    }
+
+    fn as_slice(&'a self) -> &'a [T] {
+        &self.vec
+    }
+
+    fn as_mut_slice(&'a mut self) -> &'a mut [T] {
+        &mut self.vec
+    }
 }
@@ -13,7 +13,7 @@ impl<'world> SubWorld<'world> {
         self.world
     }
 
-    pub(crate) unsafe fn world_mut(&mut self) -> &'world mut World {
+    pub(crate) unsafe fn world_mut(&mut self) -> &mut World {
         &mut *(self.world as *const World as *mut World)
     }
 }
@@ -1,10 +1,18 @@
-use crate::query::{self, IntoQuery, QueryIter};
-use crate::resource::{Readonly, ResourceSet, Resources};
+use crate::entity::Entity;
+use crate::error::EcsError;
+use crate::index::{AnyIndex, IndexRef};
+use crate::query::{self, ChunkFetch, ChunkIter, Fetch, FetchFilter, IntoQuery, QueryAccess, QueryCursor, QueryIter};
+use crate::resource::{
+    AtomicRef, AtomicRefMut, ReadScoped, Readonly, Resource, ResourceSet, Resources, ScopedMut, ScopedRef,
+    ScopedResource, WriteScoped,
+};
 use crate::subworld::SubWorld;
 use crate::type_list::{Append, Flatten};
 use crate::world::World;
 use std::marker::PhantomData;
 
+pub use ecs_derive::SystemParam;
+
 pub trait System {
     type Resources: for<'resources> ResourceSet<'resources>;
     type Queries: for<'world> QuerySet<'world>;
@@ -14,12 +22,37 @@ pub trait System {
         queries: <Self::Queries as QuerySet>::Result,
         resources: <Self::Resources as ResourceSet>::Result,
     );
+
+    fn is_thread_local(&self) -> bool {
+        <Self::Resources as ResourceSet<'static>>::thread_local()
+    }
+
+    fn access(&self) -> QueryAccess {
+        let mut access = QueryAccess::default();
+
+        <Self::Queries as QuerySet<'static>>::access(&mut access);
+        access
+    }
 }
 
 pub trait QuerySet<'world>: Sized {
     type Result: 'world;
 
     fn fetch(world: &'world mut World) -> Self::Result;
+
+    fn access(_: &mut QueryAccess) {}
+}
+
+pub trait SystemParam<'world, 'resources> {
+    type Result;
+
+    /// # Safety
+    ///
+    /// Nothing else may access the components or resources named by [`SystemParam::access`] mutably, or read the
+    /// ones it writes, while the result is alive.
+    unsafe fn fetch_unchecked(world: &'world World, resources: &'resources Resources) -> Self::Result;
+
+    fn access(_: &mut QueryAccess) {}
 }
 
 pub struct SystemQuery<'world, T: IntoQuery> {
@@ -31,6 +64,8 @@ pub struct AnySystem<R, Q, F>(F, PhantomData<(R, Q)>);
 
 pub struct SystemFn<F>(pub F);
 
+pub struct ParamSystem<P, F>(F, PhantomData<P>);
+
 impl<R, Q, F> System for AnySystem<R, Q, F>
 where
     R: for<'resources> ResourceSet<'resources>,
@@ -62,6 +97,27 @@ where
     }
 }
 
+impl<P, F> System for ParamSystem<P, F>
+where
+    P: for<'world, 'resources> SystemParam<'world, 'resources>,
+    F: for<'world, 'resources> FnMut(<P as SystemParam<'world, 'resources>>::Result),
+{
+    type Resources = Resources;
+    type Queries = World;
+
+    #[inline]
+    fn run(&mut self, world: &mut World, resources: &Resources) {
+        (self.0)(unsafe { P::fetch_unchecked(world, resources) })
+    }
+
+    fn access(&self) -> QueryAccess {
+        let mut access = QueryAccess::default();
+
+        <P as SystemParam<'static, 'static>>::access(&mut access);
+        access
+    }
+}
+
 impl<'world> QuerySet<'world> for World {
     type Result = &'world mut World;
 
@@ -78,9 +134,186 @@ impl<'world, T: IntoQuery> SystemQuery<'world, T> {
         self.query.iter(self.world.world())
     }
 
-    pub fn iter_mut<'index>(&'index mut self) -> QueryIter<'world, 'index, T::Fetch> {
+    pub fn iter_mut<'a>(&'a mut self) -> QueryIter<'a, 'a, T::Fetch> {
         self.query.iter_mut(unsafe { self.world.world_mut() })
     }
+
+    pub fn get_many_mut<'a, const N: usize>(&'a mut self, entities: [Entity; N]) -> Option<[<T::Fetch as Fetch<'a>>::Item; N]> {
+        self.query.get_many_mut(unsafe { self.world.world_mut() }, entities)
+    }
+
+    pub fn try_get_many_mut<'a, const N: usize>(
+        &'a mut self,
+        entities: [Entity; N],
+    ) -> Result<[<T::Fetch as Fetch<'a>>::Item; N], EcsError> {
+        self.query.try_get_many_mut(unsafe { self.world.world_mut() }, entities)
+    }
+
+    pub fn single(&self) -> Result<<T::Fetch as Fetch<'world>>::Item, EcsError>
+    where
+        T::Fetch: Readonly,
+    {
+        self.query.single(self.world.world())
+    }
+
+    pub fn single_mut<'a>(&'a mut self) -> Result<<T::Fetch as Fetch<'a>>::Item, EcsError> {
+        self.query.single_mut(unsafe { self.world.world_mut() })
+    }
+
+    pub fn count(&self) -> usize {
+        self.query.count(self.world.world())
+    }
+
+    pub fn iter_from_cursor(
+        &self,
+        cursor: &mut QueryCursor,
+        limit: usize,
+    ) -> impl Iterator<Item = <T::Fetch as Fetch<'world>>::Item> + 'world
+    where
+        T::Fetch: Readonly,
+    {
+        self.query.iter_from_cursor(self.world.world(), cursor, limit)
+    }
+
+    pub fn iter_from_cursor_mut<'a>(
+        &'a mut self,
+        cursor: &mut QueryCursor,
+        limit: usize,
+    ) -> impl Iterator<Item = <T::Fetch as Fetch<'a>>::Item> + 'a {
+        self.query.iter_from_cursor_mut(unsafe { self.world.world_mut() }, cursor, limit)
+    }
+
+    pub fn iter_chunks<'index>(&'index self) -> ChunkIter<'world, 'index, T::Fetch>
+    where
+        T::Fetch: Readonly + ChunkFetch<'world>,
+    {
+        self.query.iter_chunks(self.world.world())
+    }
+
+    pub fn iter_chunks_mut<'a>(&'a mut self) -> ChunkIter<'a, 'a, T::Fetch>
+    where
+        T::Fetch: ChunkFetch<'a>,
+    {
+        self.query.iter_chunks_mut(unsafe { self.world.world_mut() })
+    }
+
+    pub fn par_for_each<F>(&self, batch_size: usize, f: F)
+    where
+        T::Fetch: Readonly,
+        F: Fn(<T::Fetch as Fetch<'world>>::Item) + Sync,
+    {
+        self.query.par_for_each(self.world.world(), batch_size, f)
+    }
+
+    pub fn par_for_each_mut<'a, F>(&'a mut self, batch_size: usize, f: F)
+    where
+        F: Fn(<T::Fetch as Fetch<'a>>::Item) + Sync,
+    {
+        self.query.par_for_each_mut(unsafe { self.world.world_mut() }, batch_size, f)
+    }
+}
+
+impl<'world, 'resources, 'a, T: IntoQuery + 'world> SystemParam<'world, 'resources> for SystemQuery<'a, T> {
+    type Result = SystemQuery<'world, T>;
+
+    unsafe fn fetch_unchecked(world: &'world World, _: &'resources Resources) -> Self::Result {
+        SystemQuery {
+            world: world.subworld(),
+            query: T::query(),
+        }
+    }
+
+    fn access(access: &mut QueryAccess) {
+        let mut query = QueryAccess::default();
+
+        T::Fetch::access(&mut query);
+        access.extend(&query);
+    }
+}
+
+impl<'world, 'resources, 'a, T: Resource> SystemParam<'world, 'resources> for AtomicRef<'a, T> {
+    type Result = AtomicRef<'resources, T>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        resources.get()
+    }
+}
+
+impl<'world, 'resources, 'a, T: Resource> SystemParam<'world, 'resources> for AtomicRefMut<'a, T> {
+    type Result = AtomicRefMut<'resources, T>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        resources.get_mut()
+    }
+}
+
+impl<'world, 'resources, 'a, T: Resource> SystemParam<'world, 'resources> for Option<AtomicRef<'a, T>> {
+    type Result = Option<AtomicRef<'resources, T>>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        resources.get_optional()
+    }
+}
+
+impl<'world, 'resources, 'a, T: Resource> SystemParam<'world, 'resources> for Option<AtomicRefMut<'a, T>> {
+    type Result = Option<AtomicRefMut<'resources, T>>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        resources.get_mut_optional()
+    }
+}
+
+impl<'world, 'resources, 'a, K: ScopedResource> SystemParam<'world, 'resources> for ScopedRef<'a, K> {
+    type Result = ScopedRef<'resources, K>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        ReadScoped::<K>::fetch_unchecked(resources)
+    }
+}
+
+impl<'world, 'resources, 'a, K: ScopedResource> SystemParam<'world, 'resources> for ScopedMut<'a, K> {
+    type Result = ScopedMut<'resources, K>;
+
+    unsafe fn fetch_unchecked(_: &'world World, resources: &'resources Resources) -> Self::Result {
+        WriteScoped::<K>::fetch_unchecked(resources)
+    }
+}
+
+impl<'world, 'resources, 'a, I: AnyIndex> SystemParam<'world, 'resources> for IndexRef<'a, I> {
+    type Result = IndexRef<'world, I>;
+
+    unsafe fn fetch_unchecked(world: &'world World, _: &'resources Resources) -> Self::Result {
+        world.index()
+    }
+}
+
+impl<'world, 'resources, 'a, I: AnyIndex> SystemParam<'world, 'resources> for Option<IndexRef<'a, I>> {
+    type Result = Option<IndexRef<'world, I>>;
+
+    unsafe fn fetch_unchecked(world: &'world World, _: &'resources Resources) -> Self::Result {
+        match world.try_index() {
+            | Ok(index) => Some(index),
+            | Err(EcsError::MissingIndex { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<P, F> ParamSystem<P, F>
+where
+    P: for<'world, 'resources> SystemParam<'world, 'resources>,
+    F: for<'world, 'resources> FnMut(<P as SystemParam<'world, 'resources>>::Result),
+{
+    pub fn new(f: F) -> Self {
+        Self::try_new(f).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(f: F) -> Result<Self, EcsError> {
+        let system = ParamSystem(f, PhantomData);
+
+        system.access().check()?;
+        Ok(system)
+    }
 }
 
 pub struct AnySystemBuilder<R, Q>(PhantomData<(R, Q)>);
@@ -147,8 +380,80 @@ macro_rules! impl_query_set {
                     }
                 },)*)
             }
+
+            #[allow(unused_variables)]
+            fn access(access: &mut QueryAccess) {
+                $({
+                    let mut query = QueryAccess::default();
+
+                    $ty::Fetch::access(&mut query);
+                    access.extend(&query);
+                })*
+            }
         }
     };
 }
 
 impl_query_set!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+
+#[cfg(doctest)]
+/// ```compile_fail,E0499
+/// use ecs::component::Component;
+/// use ecs::query::Write;
+/// use ecs::system::SystemQuery;
+///
+/// #[derive(Component)]
+/// struct Pos(u32);
+///
+/// fn alias(mut query: SystemQuery<'_, Write<Pos>>) {
+///     let a = query.single_mut().unwrap();
+///     let b = query.single_mut().unwrap();
+///     std::mem::swap(a, b);
+/// }
+/// ```
+///
+/// ```compile_fail,E0499
+/// use ecs::component::Component;
+/// use ecs::entity::Entity;
+/// use ecs::query::Write;
+/// use ecs::system::SystemQuery;
+///
+/// #[derive(Component)]
+/// struct Pos(u32);
+///
+/// fn alias(mut query: SystemQuery<'_, Write<Pos>>, a: Entity, b: Entity) {
+///     let [x] = query.get_many_mut([a]).unwrap();
+///     let [y] = query.try_get_many_mut([b]).unwrap();
+///     std::mem::swap(x, y);
+/// }
+/// ```
+///
+/// ```compile_fail,E0499
+/// use ecs::component::Component;
+/// use ecs::query::{QueryCursor, Write};
+/// use ecs::system::SystemQuery;
+///
+/// #[derive(Component)]
+/// struct Pos(u32);
+///
+/// fn alias(mut query: SystemQuery<'_, Write<Pos>>, cursor: &mut QueryCursor) {
+///     let a = query.iter_from_cursor_mut(cursor, 1).next().unwrap();
+///     let b = query.iter_from_cursor_mut(cursor, 1).next().unwrap();
+///     std::mem::swap(a, b);
+/// }
+/// ```
+///
+/// ```
+/// use ecs::component::Component;
+/// use ecs::query::Write;
+/// use ecs::system::SystemQuery;
+///
+/// #[derive(Component)]
+/// struct Pos(u32);
+///
+/// fn sequential(mut query: SystemQuery<'_, Write<Pos>>) {
+///     query.single_mut().unwrap().0 += 1;
+///     query.single_mut().unwrap().0 += 1;
+/// }
+/// ```
+struct MutableBorrowsAreExclusive;
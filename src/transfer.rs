@@ -0,0 +1,201 @@
+use crate::archetype::ArchetypeLayout;
+use crate::component::ComponentIndex;
+use crate::entity::{Entity, EntityData};
+use crate::error::EcsError;
+use crate::filter::LayoutFilter;
+use crate::hook::{CommandQueue, HookKind};
+use crate::storage::BlobArchetypeStorage;
+use crate::world::World;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::Ordering;
+
+impl World {
+    pub fn move_entity_to(&mut self, other: &mut World, entity: Entity) -> Result<Entity, EcsError> {
+        if !self.contains(entity) {
+            return Err(EcsError::NoSuchEntity(entity));
+        }
+
+        let data = self.entities.get(entity).unwrap();
+
+        other.check_capacity(&self.severed_layout(entity, &self.archetypes[data.archetype().0 as usize].layout))?;
+        self.sever_relations(entity);
+
+        let data = self.entities.get(entity).unwrap();
+        let layout = ArchetypeLayout::clone(&self.archetypes[data.archetype().0 as usize].layout);
+
+        other.adopt(self, &layout);
+
+        let mut commands = CommandQueue::default();
+
+        self.run_hooks(HookKind::Remove, entity, &layout.components, &mut commands);
+
+        let data = self.entities.get(entity).unwrap();
+        let to = other.get_or_register_archetype(layout);
+
+        for &ty in &other.archetypes[to.0 as usize].layout.components {
+            let storage = self.components.get_any_mut(ty).unwrap();
+
+            storage.move_to(data.archetype(), data.component(), other.components.get_any_mut(ty).unwrap(), to);
+        }
+
+        let archetype = &mut self.archetypes[data.archetype().0 as usize];
+        let comp_index = data.component().0 as usize;
+        let _ = archetype.entities.swap_remove(comp_index);
+
+        if comp_index < archetype.entities.len() {
+            let swapped = archetype.entities[comp_index];
+            self.entities.set(swapped, data);
+        }
+
+        self.entities.remove(entity);
+
+        let id = other.allocate_entity();
+        let archetype = &mut other.archetypes[to.0 as usize];
+
+        other
+            .entities
+            .set(id, EntityData(to, ComponentIndex(archetype.entities.len() as u32)));
+        archetype.entities.push(id);
+
+        let mut spawned = CommandQueue::default();
+
+        other.spawned(id, &mut spawned);
+        spawned.apply(other);
+        commands.apply(self);
+        Ok(id)
+    }
+
+    pub fn merge(&mut self, mut other: World) -> Result<HashMap<Entity, Entity>, EcsError> {
+        for archetype in other.archetypes.iter().filter(|a| !a.entities.is_empty()) {
+            self.check_capacity(&archetype.layout)?;
+        }
+
+        let mut entities = HashMap::new();
+        let mut moved = Vec::new();
+
+        for archetype in std::mem::take(&mut other.archetypes) {
+            if archetype.entities.is_empty() {
+                continue;
+            }
+
+            let layout = ArchetypeLayout::clone(&archetype.layout);
+
+            self.adopt(&other, &layout);
+
+            let to = self.get_or_register_archetype(layout);
+
+            for &ty in &archetype.layout.components {
+                let storage = other.components.get_any_mut(ty).unwrap();
+
+                storage.move_column(archetype.index, self.components.get_any_mut(ty).unwrap(), to);
+            }
+
+            let ids = archetype.entities.iter().map(|_| self.allocate_entity()).collect::<Vec<_>>();
+            let dest = &mut self.archetypes[to.0 as usize];
+            let base = ComponentIndex(dest.entities.len() as u32);
+
+            dest.entities.extend_from_slice(&ids);
+            self.entities.insert(&ids, to, base);
+            entities.extend(archetype.entities.iter().copied().zip(ids.iter().copied()));
+            moved.extend(ids);
+        }
+
+        self.adopt_relations(std::mem::take(&mut other.relations), &entities);
+
+        let mut commands = CommandQueue::default();
+
+        for entity in moved {
+            self.spawned(entity, &mut commands);
+        }
+
+        commands.apply(self);
+        Ok(entities)
+    }
+
+    pub fn split<F: LayoutFilter>(&mut self, filter: &F) -> World {
+        let mut other = World::default();
+        let matched = self
+            .archetypes
+            .iter()
+            .filter(|a| !a.entities.is_empty() && filter.matches(&a.layout.components))
+            .map(|a| a.index)
+            .collect::<Vec<_>>();
+        let moved = matched
+            .iter()
+            .flat_map(|a| self.archetypes[a.0 as usize].entities.iter().copied())
+            .collect::<HashSet<_>>();
+        let mut commands = CommandQueue::default();
+
+        for &entity in &moved {
+            let data = self.entities.get(entity).unwrap();
+            let layout = self.archetypes[data.archetype().0 as usize].layout.clone();
+
+            self.run_hooks(HookKind::Remove, entity, &layout.components, &mut commands);
+        }
+
+        for archetype in matched {
+            let layout = ArchetypeLayout::clone(&self.archetypes[archetype.0 as usize].layout);
+
+            other.adopt(self, &layout);
+
+            let to = other.get_or_register_archetype(layout);
+
+            for &ty in &other.archetypes[to.0 as usize].layout.components {
+                let storage = self.components.get_any_mut(ty).unwrap();
+
+                storage.move_column(archetype, other.components.get_any_mut(ty).unwrap(), to);
+            }
+
+            let entities = std::mem::take(&mut self.archetypes[archetype.0 as usize].entities);
+
+            for &entity in &entities {
+                self.entities.remove(entity);
+            }
+
+            other.entities.insert(&entities, to, ComponentIndex(0));
+            other.archetypes[to.0 as usize].entities = entities;
+        }
+
+        other
+            .entity_counter
+            .store(self.entity_counter.load(Ordering::SeqCst), Ordering::SeqCst);
+        self.split_relations(&mut other, &moved);
+
+        let mut spawned = CommandQueue::default();
+
+        for &entity in &moved {
+            other.spawned(entity, &mut spawned);
+        }
+
+        spawned.apply(&mut other);
+        commands.apply(self);
+        other
+    }
+
+    pub(crate) fn allocate_entity(&self) -> Entity {
+        loop {
+            let entity = Entity(self.entity_counter.fetch_add(1, Ordering::SeqCst));
+
+            if !self.contains(entity) {
+                return entity;
+            }
+        }
+    }
+
+    pub(crate) fn adopt(&mut self, from: &World, layout: &ArchetypeLayout) {
+        for &ty in &layout.components {
+            if let Some(info) = from.registry().get(ty) {
+                self.registry.adopt(ty, info);
+            }
+
+            if self.components.get_any(ty).is_none() {
+                if let Some(storage) = from.components.get_any(ty).and_then(|s| s.as_blob()) {
+                    let descriptor = storage.shared_descriptor();
+
+                    self.components
+                        .get_or_insert(ty, move || Box::new(BlobArchetypeStorage::new(descriptor)));
+                }
+            }
+        }
+    }
+}
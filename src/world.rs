@@ -1,24 +1,42 @@
-use crate::archetype::{Archetype, ArchetypeDescriptor, ArchetypeIndex, ArchetypeLayout};
-use crate::component::{Component, ComponentSource};
+use crate::archetype::{Archetype, ArchetypeIndex, ArchetypeLayout};
+use crate::component::{Component, ComponentDescriptor, ComponentId, ComponentIndex, ComponentSource};
 use crate::entity::{Entity, EntityData, EntityMap};
+use crate::error::EcsError;
+use crate::hook::{CommandQueue, DeferredWorld, HookKind};
+use crate::index::Indexes;
 use crate::insert::{EntityInserter, EntitySource};
-use crate::storage::{Components, Storage};
+use crate::prefab::CloneFns;
+use crate::reflect::{Reflect, ReflectFns};
+use crate::registry::{ComponentInfo, Registry};
+use crate::relation::RelationIndex;
+use crate::replication::Replicate;
+use crate::snapshot::Snapshot;
+use crate::storage::{change_tick, AnyArchetypeStorage, ArchetypeStorage, BlobArchetypeStorage, Components, Storage};
 use crate::subworld::{AnyWorld, SubWorld};
+use std::any::type_name;
+use std::hash::Hash;
 use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 
 #[derive(Default)]
 pub struct World {
-    archetypes: Vec<Archetype>,
-    components: Components,
-    entities: EntityMap,
-    entity_counter: AtomicU64,
+    pub(crate) archetypes: Vec<Archetype>,
+    pub(crate) components: Components,
+    pub(crate) entities: EntityMap,
+    pub(crate) entity_counter: AtomicU64,
+    pub(crate) indexes: Indexes,
+    pub(crate) registry: Registry,
+    pub(crate) relations: RelationIndex,
+    pub(crate) snapshot: Option<Snapshot>,
 }
 
 pub struct Entry<'a> {
     data: EntityData,
     world: &'a mut World,
+    commands: CommandQueue,
 }
 
+#[derive(Clone, Copy)]
 pub struct StorageAccess<'world> {
     components: &'world Components,
     archetypes: &'world [Archetype],
@@ -27,37 +45,85 @@ pub struct StorageAccess<'world> {
 
 impl World {
     pub fn create<T: ComponentSource>(&mut self, components: T) -> Entity {
-        let arch_index = self.get_archetype_index::<T>();
-        let archetype = &mut self.archetypes[arch_index.0 as usize];
-        let entities = EntitySource::new(&self.entity_counter);
-        let mut inserter = EntityInserter::new(self.components.edit(), archetype, entities);
+        self.try_create(components).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        components.insert_components(&mut inserter);
+    pub fn try_create<T: ComponentSource>(&mut self, components: T) -> Result<Entity, EcsError> {
+        let layout = components.layout()?;
 
-        let (component, entities) = inserter.inserted();
-        let replaced = self.entities.insert(entities, arch_index, component);
-        let result = entities[0];
+        self.spawn(None, layout, move |inserter| components.insert_components(inserter))
+    }
 
-        for data in replaced {
-            self.remove_data(data);
+    pub fn create_with_id<T: ComponentSource>(&mut self, id: Entity, components: T) {
+        self.try_create_with_id(id, components)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_create_with_id<T: ComponentSource>(&mut self, id: Entity, components: T) -> Result<(), EcsError> {
+        let layout = components.layout()?;
+        let mut required = layout.clone();
+
+        self.registry.require(&mut required);
+
+        let replaced = self.entities.get(id).map(|data| &*self.archetypes[data.archetype().0 as usize].layout);
+
+        if replaced != Some(&required) {
+            self.check_capacity(&required)?;
         }
 
-        result
+        self.remove(id);
+        self.spawn(Some(id), layout, move |inserter| components.insert_components(inserter))
+            .map(drop)
+    }
+
+    /// # Safety
+    ///
+    /// For every component whose descriptor has a drop fn, the bytes must be a valid value of that component type. The
+    /// value is moved into the world and must not be used or dropped by the caller afterwards.
+    pub unsafe fn create_dynamic(&mut self, components: &[(ComponentId, &[u8])]) -> Entity {
+        self.try_create_dynamic(components)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
 
-    pub fn create_with_id<T: ComponentSource>(&mut self, id: Entity, components: T) {
-        self.remove(id);
+    /// # Safety
+    ///
+    /// See [`World::create_dynamic`].
+    pub unsafe fn try_create_dynamic(&mut self, components: &[(ComponentId, &[u8])]) -> Result<Entity, EcsError> {
+        let mut layout = ArchetypeLayout::default();
 
-        let arch_index = self.get_archetype_index::<T>();
-        let archetype = &mut self.archetypes[arch_index.0 as usize];
-        let entities = EntitySource::from_id(id, &self.entity_counter);
-        let mut inserter = EntityInserter::new(self.components.edit(), archetype, entities);
+        for &(id, bytes) in components {
+            let name = self.check_dynamic(id, bytes)?;
 
-        components.insert_components(&mut inserter);
+            if layout.components.contains(&id) {
+                return Err(EcsError::DuplicateComponent { component: name });
+            }
 
-        let (component, entities) = inserter.inserted();
+            layout.add_dynamic(id);
+        }
+
+        self.spawn(None, layout, |inserter| {
+            for &(id, bytes) in components {
+                unsafe { inserter.any_component(id).extend_memcpy(bytes.as_ptr(), 1) };
+            }
+
+            inserter.finish_entity();
+        })
+    }
+
+    pub fn register_dynamic(&mut self, descriptor: ComponentDescriptor) -> ComponentId {
+        let id = ComponentId::next_dynamic();
+        let descriptor = Arc::new(descriptor);
+
+        let info = self.registry.register_dynamic(id, descriptor.name());
+
+        if descriptor.drop_fn().is_none() {
+            info.set_clone_fns(CloneFns::blob());
+        }
+
+        self.components
+            .get_or_insert(id, move || Box::new(BlobArchetypeStorage::new(descriptor)));
 
-        self.entities.insert(entities, arch_index, component);
+        id
     }
 
     pub fn contains(&self, entity: Entity) -> bool {
@@ -65,25 +131,217 @@ impl World {
     }
 
     pub fn remove(&mut self, entity: Entity) -> bool {
-        if let Some(data) = self.entities.remove(entity) {
-            self.remove_data(data);
-            true
-        } else {
-            false
-        }
+        self.try_remove(entity).is_ok()
+    }
+
+    pub fn try_remove(&mut self, entity: Entity) -> Result<(), EcsError> {
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let mut commands = CommandQueue::default();
+
+        self.remove_data(data, &mut commands);
+        self.entities.remove(entity);
+        self.unlink_relations(entity);
+        commands.apply(self);
+        Ok(())
     }
 
     pub fn entry(&mut self, entity: Entity) -> Option<Entry> {
-        self.entities.get(entity).map(move |data| Entry { data, world: self })
+        self.try_entry(entity).ok()
+    }
+
+    pub fn try_entry(&mut self, entity: Entity) -> Result<Entry, EcsError> {
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+
+        Ok(Entry {
+            data,
+            world: self,
+            commands: CommandQueue::default(),
+        })
+    }
+
+    pub fn register<T: Component>(&mut self) -> &mut ComponentInfo {
+        self.registry.register::<T>()
+    }
+
+    pub fn register_clone<T: Component + Clone>(&mut self) -> &mut ComponentInfo {
+        self.registry.register_clone::<T>()
+    }
+
+    pub fn register_reflect<T: Component + Reflect>(&mut self) -> &mut ComponentInfo {
+        self.registry.register_reflect::<T>()
+    }
+
+    pub fn register_hash<T: Component + Hash>(&mut self) -> &mut ComponentInfo {
+        self.registry.register_hash::<T>()
+    }
+
+    /// Deltas identify replicated components by registration order, so both worlds must register them in the same
+    /// order.
+    pub fn register_replicated<T: Component + Replicate>(&mut self) -> &mut ComponentInfo {
+        self.registry.register_replicated::<T>()
+    }
+
+    pub fn register_replicated_dynamic(&mut self, id: ComponentId) -> &mut ComponentInfo {
+        let descriptor = self
+            .components
+            .get_any(id)
+            .and_then(|s| s.as_blob())
+            .map(|s| s.descriptor())
+            .expect("dynamic components must be registered with the world before use");
+        let name = descriptor.name().to_owned();
+
+        assert!(
+            descriptor.drop_fn().is_none(),
+            "dynamic component `{}` has a drop fn and cannot be replicated as raw bytes",
+            name
+        );
+
+        self.registry.register_replicated_dynamic(id, &name)
+    }
+
+    pub fn change_tick(&self) -> u64 {
+        change_tick()
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub(crate) fn component<T: Component>(&self, entity: Entity) -> Option<&T> {
+        self.storage_access().component(entity)
+    }
+
+    pub(crate) fn component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        let data = self.entities.get(entity)?;
+
+        self.components
+            .get_mut::<T>()
+            .and_then(|s| s.get_component_mut(data.archetype(), data.component()))
     }
 
     pub(crate) fn subworld(&self) -> SubWorld {
         SubWorld { world: self }
     }
 
-    fn remove_data(&mut self, data: EntityData) {
+    fn spawn<F>(&mut self, id: Option<Entity>, layout: ArchetypeLayout, insert: F) -> Result<Entity, EcsError>
+    where
+        F: FnOnce(&mut EntityInserter<'_>),
+    {
+        self.spawn_batch(id, layout, insert).map(|spawned| spawned[0])
+    }
+
+    pub(crate) fn spawn_batch<F>(
+        &mut self,
+        id: Option<Entity>,
+        mut layout: ArchetypeLayout,
+        insert: F,
+    ) -> Result<Vec<Entity>, EcsError>
+    where
+        F: FnOnce(&mut EntityInserter<'_>),
+    {
+        let required = self.registry.require(&mut layout);
+
+        self.check_capacity(&layout)?;
+
+        let arch_index = self.get_or_register_archetype(layout);
+        let archetype = &mut self.archetypes[arch_index.0 as usize];
+        let entities = match id {
+            | Some(id) => EntitySource::from_id(id, &self.entity_counter),
+            | None => EntitySource::new(&self.entity_counter),
+        };
+        let mut inserter = EntityInserter::new(self.components.edit(), archetype, entities);
+
+        insert(&mut inserter);
+
+        let (component, entities) = inserter.inserted();
+        let replaced = self.entities.insert(entities, arch_index, component);
+        let spawned = entities.to_vec();
+        let mut commands = CommandQueue::default();
+
+        for req in &required {
+            req.insert(&mut self.components, arch_index, spawned.len());
+        }
+
+        for data in replaced {
+            self.remove_data(data, &mut commands);
+        }
+
+        for &entity in &spawned {
+            self.spawned(entity, &mut commands);
+        }
+
+        commands.apply(self);
+        Ok(spawned)
+    }
+
+    pub(crate) fn check_capacity(&self, layout: &ArchetypeLayout) -> Result<(), EcsError> {
+        let archetype = match self.archetypes.iter().find(|a| &*a.layout == layout) {
+            | Some(archetype) => archetype.index,
+            | None => return Ok(()),
+        };
+
+        for &ty in &layout.components {
+            if let Some(storage) = self.components.get_any(ty).filter(|s| s.is_full(archetype)) {
+                return Err(EcsError::StorageFull {
+                    component: storage.component_name().to_owned(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_dynamic(&self, id: ComponentId, bytes: &[u8]) -> Result<String, EcsError> {
+        let storage = self
+            .components
+            .get_any(id)
+            .and_then(|s| s.as_blob())
+            .ok_or(EcsError::UnregisteredComponent { component: id })?;
+        let size = storage.descriptor().layout().size();
+
+        if bytes.len() != size {
+            return Err(EcsError::SizeMismatch {
+                component: storage.descriptor().name().to_owned(),
+                expected: size,
+                found: bytes.len(),
+            });
+        }
+
+        Ok(storage.descriptor().name().to_owned())
+    }
+
+    pub(crate) fn spawned(&mut self, entity: Entity, commands: &mut CommandQueue) {
+        let data = self.entities.get(entity).unwrap();
+        let layout = self.archetypes[data.archetype().0 as usize].layout.clone();
+
+        self.run_hooks(HookKind::Add, entity, &layout.components, commands);
+        self.run_hooks(HookKind::Insert, entity, &layout.components, commands);
+    }
+
+    pub(crate) fn run_hooks(&mut self, kind: HookKind, entity: Entity, components: &[ComponentId], commands: &mut CommandQueue) {
+        if !self.indexes.is_empty() {
+            let access = StorageAccess {
+                components: &self.components,
+                archetypes: &self.archetypes,
+                entities: &self.entities,
+            };
+
+            self.indexes.notify(kind, entity, components, access);
+        }
+
+        for hook in self.registry.hooks(kind, components) {
+            hook(&mut DeferredWorld::new(self, commands), entity);
+        }
+    }
+
+    fn remove_data(&mut self, data: EntityData, commands: &mut CommandQueue) {
         let arch_index = data.archetype().0 as usize;
         let comp_index = data.component().0 as usize;
+        let entity = self.archetypes[arch_index].entities[comp_index];
+        let layout = self.archetypes[arch_index].layout.clone();
+
+        self.run_hooks(HookKind::Remove, entity, &layout.components, commands);
+
         let archetype = &mut self.archetypes[arch_index];
         let _ = archetype.entities.swap_remove(comp_index);
 
@@ -99,9 +357,171 @@ impl World {
         }
     }
 
-    fn get_archetype_index<T: ArchetypeDescriptor>(&mut self) -> ArchetypeIndex {
-        let layout = T::layout();
+    fn insert_component<T: Component>(
+        &mut self,
+        entity: Entity,
+        component: T,
+        commands: &mut CommandQueue,
+    ) -> Result<EntityData, EcsError> {
+        let ty = ComponentId::of::<T>();
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let archetype = &self.archetypes[data.archetype().0 as usize];
+
+        if archetype.layout.components.contains(&ty) {
+            *self.component_mut(entity).unwrap() = component;
+            self.run_hooks(HookKind::Insert, entity, &[ty], commands);
+            return Ok(data);
+        }
+
+        self.extend_entity(entity, data, ty, ArchetypeStorage::<T>::any, commands, |components, to| {
+            components
+                .get_mut::<T>()
+                .unwrap()
+                .extend(to, std::iter::once(component));
+        })
+    }
+
+    unsafe fn insert_dynamic(
+        &mut self,
+        entity: Entity,
+        id: ComponentId,
+        bytes: &[u8],
+        commands: &mut CommandQueue,
+    ) -> Result<EntityData, EcsError> {
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+
+        self.check_dynamic(id, bytes)?;
+
+        if self.archetypes[data.archetype().0 as usize].layout.components.contains(&id) {
+            let storage = self.components.get_any_mut(id).and_then(|s| s.as_blob_mut()).unwrap();
+
+            unsafe { storage.replace(data.archetype(), data.component(), bytes.as_ptr()) };
+            self.run_hooks(HookKind::Insert, entity, &[id], commands);
+            return Ok(data);
+        }
+
+        self.extend_entity(entity, data, id, BlobArchetypeStorage::unregistered, commands, |components, to| {
+            unsafe { components.get_any_mut(id).unwrap().extend_memcpy(to, bytes.as_ptr(), 1) };
+        })
+    }
+
+    fn extend_entity<F>(
+        &mut self,
+        entity: Entity,
+        data: EntityData,
+        ty: ComponentId,
+        ctor: fn() -> Box<dyn AnyArchetypeStorage>,
+        commands: &mut CommandQueue,
+        insert: F,
+    ) -> Result<EntityData, EcsError>
+    where
+        F: FnOnce(&mut Components, ArchetypeIndex),
+    {
+        let mut layout = ArchetypeLayout::clone(&self.archetypes[data.archetype().0 as usize].layout);
+
+        layout.add_any(ty, ctor);
+
+        let required = self.registry.require(&mut layout);
+
+        self.check_capacity(&layout)?;
+
+        let to = self.get_or_register_archetype(layout);
+        let data = self.move_entity(entity, data, to);
+
+        insert(&mut self.components, to);
+
+        for req in &required {
+            req.insert(&mut self.components, to, 1);
+        }
+
+        let added = std::iter::once(ty).chain(required.iter().map(|req| req.ty)).collect::<Vec<_>>();
+
+        self.run_hooks(HookKind::Add, entity, &added, commands);
+        self.run_hooks(HookKind::Insert, entity, &added, commands);
+        Ok(data)
+    }
+
+    fn take_component<T: Component>(
+        &mut self,
+        entity: Entity,
+        commands: &mut CommandQueue,
+    ) -> Result<(EntityData, T), EcsError> {
+        let ty = ComponentId::of::<T>();
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let mut layout = ArchetypeLayout::clone(&self.archetypes[data.archetype().0 as usize].layout);
+
+        if !layout.remove(ty) {
+            return Err(EcsError::MissingComponent {
+                entity,
+                component: type_name::<T>().to_owned(),
+            });
+        }
+
+        self.check_capacity(&layout)?;
+        self.run_hooks(HookKind::Remove, entity, &[ty], commands);
+
+        let component = self
+            .components
+            .get_mut::<T>()
+            .and_then(|s| s.remove(data.archetype(), data.component()))
+            .unwrap();
+
+        let to = self.get_or_register_archetype(layout);
+
+        Ok((self.move_entity(entity, data, to), component))
+    }
+
+    fn take_dynamic(&mut self, entity: Entity, id: ComponentId, commands: &mut CommandQueue) -> Result<EntityData, EcsError> {
+        let data = self.entities.get(entity).ok_or(EcsError::NoSuchEntity(entity))?;
+        let mut layout = ArchetypeLayout::clone(&self.archetypes[data.archetype().0 as usize].layout);
+
+        if !layout.remove(id) {
+            return Err(EcsError::MissingComponent {
+                entity,
+                component: self.registry.get(id).map_or_else(|| format!("{:?}", id), |i| i.name().to_owned()),
+            });
+        }
+
+        self.check_capacity(&layout)?;
+        self.run_hooks(HookKind::Remove, entity, &[id], commands);
+        self.components
+            .get_any_mut(id)
+            .unwrap()
+            .swap_remove(data.archetype(), data.component());
+
+        let to = self.get_or_register_archetype(layout);
+
+        Ok(self.move_entity(entity, data, to))
+    }
+
+    fn move_entity(&mut self, entity: Entity, data: EntityData, to: ArchetypeIndex) -> EntityData {
+        let comp_index = data.component().0 as usize;
+        let layout = self.archetypes[to.0 as usize].layout.clone();
+        let from = &mut self.archetypes[data.archetype().0 as usize];
+        let _ = from.entities.swap_remove(comp_index);
+
+        for &ty in &layout.components {
+            if from.layout.components.contains(&ty) {
+                let storage = self.components.get_any_mut(ty).unwrap();
+
+                storage.move_component(data.archetype(), data.component(), to);
+            }
+        }
+
+        if comp_index < from.entities.len() {
+            let swapped = from.entities[comp_index];
+            self.entities.set(swapped, data);
+        }
+
+        let archetype = &mut self.archetypes[to.0 as usize];
+        let moved = EntityData(to, ComponentIndex(archetype.entities.len() as u32));
+
+        archetype.entities.push(entity);
+        self.entities.set(entity, moved);
+        moved
+    }
 
+    pub(crate) fn get_or_register_archetype(&mut self, layout: ArchetypeLayout) -> ArchetypeIndex {
         match self.archetypes.iter().position(|a| &*a.layout == &layout) {
             | Some(idx) => ArchetypeIndex(idx as u32),
             | None => self.register_archetype(layout),
@@ -145,6 +565,15 @@ impl<'world> StorageAccess<'world> {
     pub fn entities(&self) -> &'world EntityMap {
         self.entities
     }
+
+    pub fn component<T: Component>(&self, entity: Entity) -> Option<&'world T> {
+        let data = self.entities.get(entity)?;
+
+        self.components
+            .get::<T>()
+            .and_then(|s| s.get(data.archetype()))
+            .and_then(|s| s.get(data.component()))
+    }
 }
 
 impl<'a> Entry<'a> {
@@ -166,7 +595,176 @@ impl<'a> Entry<'a> {
         self.world
             .components
             .get_mut::<T>()
-            .and_then(|s| s.get_mut(archetype))
-            .and_then(|s| s.get_mut(component))
+            .and_then(|s| s.get_component_mut(archetype, component))
+    }
+
+    pub fn try_component<T: Component>(&self) -> Result<&T, EcsError> {
+        let entity = self.entity();
+
+        self.component().ok_or_else(|| EcsError::MissingComponent {
+            entity,
+            component: type_name::<T>().to_owned(),
+        })
+    }
+
+    pub fn try_component_mut<T: Component>(&mut self) -> Result<&mut T, EcsError> {
+        let entity = self.entity();
+
+        self.component_mut().ok_or_else(|| EcsError::MissingComponent {
+            entity,
+            component: type_name::<T>().to_owned(),
+        })
+    }
+
+    pub fn add_component<T: Component>(&mut self, component: T) {
+        self.try_add_component(component)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_add_component<T: Component>(&mut self, component: T) -> Result<(), EcsError> {
+        let entity = self.entity();
+
+        self.data = self
+            .world
+            .insert_component(entity, component, &mut self.commands)?;
+        Ok(())
+    }
+
+    pub fn remove_component<T: Component>(&mut self) -> Option<T> {
+        match self.try_remove_component() {
+            | Ok(component) => Some(component),
+            | Err(EcsError::MissingComponent { .. }) => None,
+            | Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn try_remove_component<T: Component>(&mut self) -> Result<T, EcsError> {
+        let entity = self.entity();
+        let (data, component) = self.world.take_component::<T>(entity, &mut self.commands)?;
+
+        self.data = data;
+        Ok(component)
+    }
+
+    pub fn dynamic(&self, id: ComponentId) -> Option<&[u8]> {
+        self.world
+            .components
+            .get_any(id)
+            .and_then(|s| s.as_blob())
+            .and_then(|s| s.get(self.data.archetype()))
+            .and_then(|s| s.bytes(self.data.component()))
+    }
+
+    /// # Safety
+    ///
+    /// See [`BlobStorage::bytes_mut`](crate::storage::BlobStorage::bytes_mut).
+    pub unsafe fn dynamic_mut(&mut self, id: ComponentId) -> Option<&mut [u8]> {
+        let component = self.data.component();
+        let archetype = self.data.archetype();
+
+        self.world
+            .components
+            .get_any_mut(id)
+            .and_then(|s| s.as_blob_mut())
+            .and_then(|s| s.bytes_mut(archetype, component))
+    }
+
+    /// # Safety
+    ///
+    /// If the descriptor of `id` has a drop fn, `bytes` must be a valid value of that component type. The value is moved
+    /// into the world and must not be used or dropped by the caller afterwards.
+    pub unsafe fn add_dynamic(&mut self, id: ComponentId, bytes: &[u8]) {
+        self.try_add_dynamic(id, bytes)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// # Safety
+    ///
+    /// See [`Entry::add_dynamic`].
+    pub unsafe fn try_add_dynamic(&mut self, id: ComponentId, bytes: &[u8]) -> Result<(), EcsError> {
+        let entity = self.entity();
+
+        self.data = self
+            .world
+            .insert_dynamic(entity, id, bytes, &mut self.commands)?;
+        Ok(())
+    }
+
+    pub fn remove_dynamic(&mut self, id: ComponentId) -> bool {
+        match self.try_remove_dynamic(id) {
+            | Ok(()) => true,
+            | Err(EcsError::MissingComponent { .. }) => false,
+            | Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn try_remove_dynamic(&mut self, id: ComponentId) -> Result<(), EcsError> {
+        let entity = self.entity();
+
+        self.data = self.world.take_dynamic(entity, id, &mut self.commands)?;
+        Ok(())
+    }
+
+    pub fn components(&self) -> &[ComponentId] {
+        &self.world.archetypes[self.data.archetype().0 as usize].layout.components
+    }
+
+    pub fn reflect(&self, type_name: &str) -> Option<&dyn Reflect> {
+        let (id, fns) = self.reflect_fns(type_name)?;
+
+        self.world
+            .components
+            .get_any(id)
+            .and_then(|s| s.get_any(self.data.archetype(), self.data.component()))
+            .and_then(|any| fns.get(any))
+    }
+
+    pub fn reflect_mut(&mut self, type_name: &str) -> Option<&mut dyn Reflect> {
+        let (id, fns) = self.reflect_fns(type_name)?;
+        let component = self.data.component();
+        let archetype = self.data.archetype();
+
+        self.world
+            .components
+            .get_any_mut(id)
+            .and_then(|s| s.get_any_mut(archetype, component))
+            .and_then(|any| fns.get_mut(any))
+    }
+
+    pub fn reflected(&self) -> impl Iterator<Item = &dyn Reflect> + '_ {
+        let component = self.data.component();
+        let archetype = self.data.archetype();
+
+        self.components().iter().filter_map(move |&id| {
+            let fns = self.world.registry.get(id)?.reflect_fns()?;
+            let any = self.world.components.get_any(id)?.get_any(archetype, component)?;
+
+            fns.get(any)
+        })
+    }
+
+    fn reflect_fns(&self, type_name: &str) -> Option<(ComponentId, ReflectFns)> {
+        self.components().iter().find_map(|&id| {
+            self.world
+                .registry
+                .get(id)
+                .filter(|info| info.name() == type_name)
+                .and_then(|info| info.reflect_fns())
+                .map(|fns| (id, fns))
+        })
+    }
+
+    pub fn entity(&self) -> Entity {
+        let archetype = &self.world.archetypes[self.data.archetype().0 as usize];
+
+        archetype.entities[self.data.component().0 as usize]
+    }
+}
+
+impl<'a> Drop for Entry<'a> {
+    fn drop(&mut self) {
+        if !self.commands.is_empty() {
+            self.commands.apply(self.world);
+        }
     }
 }
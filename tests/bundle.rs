@@ -0,0 +1,50 @@
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn nested_tuples_are_flattened() {
+    let mut world = World::default();
+    let a = world.create(((Pos(1), Vel(2)), (Tag,)));
+    let b = world.create((Pos(3), ((Vel(4),), ())));
+
+    assert_eq!(<(Read<Pos>, Read<Vel>, Read<Tag>)>::query().get(&world, a), Some((&Pos(1), &Vel(2), &Tag)));
+    assert_eq!(<(Read<Pos>, Read<Vel>)>::query().get(&world, b), Some((&Pos(3), &Vel(4))));
+    assert_eq!(Read::<Tag>::query().get(&world, b), None);
+}
+
+#[test]
+fn optional_parts_are_skipped() {
+    let mut world = World::default();
+    let a = world.create((Pos(1), Some(Vel(2)), None::<Tag>));
+    let b = world.create((Pos(1), None::<(Vel, Tag)>));
+
+    assert_eq!(Read::<Vel>::query().get(&world, a), Some(&Vel(2)));
+    assert_eq!(Read::<Tag>::query().get(&world, a), None);
+    assert_eq!(<(Entity, Read<Pos>)>::query().iter(&world).count(), 2);
+    assert_eq!(Read::<Vel>::query().get(&world, b), None);
+}
+
+#[test]
+fn duplicate_components_are_an_error() {
+    let mut world = World::default();
+
+    assert!(matches!(
+        world.try_create((Pos(1), (Vel(1), Pos(2)))),
+        Err(EcsError::DuplicateComponent { .. })
+    ));
+    assert!(matches!(world.try_create((Some(Pos(1)), Pos(2))), Err(EcsError::DuplicateComponent { .. })));
+    assert!(world.try_create((None::<Pos>, Pos(2))).is_ok());
+    assert_eq!(Read::<Pos>::query().iter(&world).count(), 1);
+}
@@ -0,0 +1,93 @@
+use ecs::checksum::DiffKind;
+use ecs::component::{Component, ComponentDescriptor};
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::world::World;
+use std::alloc::Layout;
+use std::hash::Hasher;
+
+#[derive(Component, Debug, Clone, PartialEq, Hash)]
+struct Pos(i32);
+
+#[derive(Component, Debug, Clone, PartialEq, Hash)]
+struct Name(String);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Unhashed(String);
+
+unsafe fn hash_u32(ptr: *const u8, hasher: &mut dyn Hasher) {
+    hasher.write_u32(ptr.cast::<u32>().read_unaligned());
+}
+
+fn build(reversed: bool) -> World {
+    let mut world = World::default();
+
+    world.register_hash::<Pos>();
+    world.register_hash::<Name>();
+
+    let health = world.register_dynamic(ComponentDescriptor::new("Health", Layout::new::<u32>()).with_hash(hash_u32));
+
+    if reversed {
+        world.create_with_id(Entity(2), (Name("b".to_string()),));
+        world.create_with_id(Entity(1), (Name("a".to_string()), Pos(1)));
+    } else {
+        world.create_with_id(Entity(1), (Pos(1), Name("a".to_string())));
+        world.create_with_id(Entity(2), (Name("b".to_string()),));
+    }
+
+    unsafe { world.entry(Entity(2)).unwrap().add_dynamic(health, &3u32.to_ne_bytes()) };
+    world
+}
+
+#[test]
+fn checksum_ignores_insertion_order() {
+    let a = build(false);
+    let b = build(true);
+
+    assert_eq!(a.checksum().unwrap(), b.checksum().unwrap());
+    assert!(a.diff(&b).unwrap().is_empty());
+}
+
+#[test]
+fn checksum_hashes_heap_data_by_value() {
+    let a = build(false);
+    let b = build(false);
+
+    assert_eq!(a.checksum().unwrap(), b.checksum().unwrap());
+}
+
+#[test]
+fn diff_reports_changes() {
+    let a = build(false);
+    let mut b = build(false);
+
+    b.entry(Entity(1)).unwrap().component_mut::<Name>().unwrap().0 = "z".to_string();
+    b.entry(Entity(2)).unwrap().add_component(Pos(0));
+    let spawned = b.create((Pos(1),));
+
+    assert_ne!(a.checksum().unwrap(), b.checksum().unwrap());
+
+    let diff = a.diff(&b).unwrap();
+    assert_eq!(diff.spawned, vec![spawned]);
+    assert_eq!(diff.components.len(), 2);
+    assert_eq!(diff.components[0].kind, DiffKind::Changed);
+    assert_eq!(diff.components[1].kind, DiffKind::Added);
+}
+
+#[test]
+fn typed_component_without_hash_is_an_error() {
+    let mut world = build(false);
+
+    world.create((Unhashed("x".to_string()),));
+    assert!(matches!(world.checksum(), Err(EcsError::NotHashable { .. })));
+    assert!(matches!(build(false).diff(&world), Err(EcsError::NotHashable { .. })));
+}
+
+#[test]
+fn dynamic_component_without_hash_is_an_error() {
+    let mut world = build(false);
+    let padded = world.register_dynamic(ComponentDescriptor::new("Padded", Layout::new::<(u8, u32)>()));
+
+    unsafe { world.create_dynamic(&[(padded, &[0; 8])]) };
+    assert!(matches!(world.checksum(), Err(EcsError::NotHashable { component }) if component == "Padded"));
+}
@@ -0,0 +1,53 @@
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::query::{Has, IntoQuery, Read, TryRead, Write};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(f32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(f32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn chunks_are_parallel_slices_per_archetype() {
+    let mut world = World::default();
+    let a = world.create((Pos(0.0), Vel(1.0)));
+    let b = world.create((Pos(0.0), Vel(2.0)));
+    let c = world.create((Pos(0.0), Vel(3.0), Tag));
+    world.create((Pos(0.0),));
+
+    let mut seen = Vec::new();
+
+    for (entities, (pos, vel, tagged)) in <(Write<Pos>, Read<Vel>, Has<Tag>)>::query().iter_chunks_mut(&mut world) {
+        assert_eq!(entities.len(), pos.len());
+        assert_eq!(entities.len(), vel.len());
+
+        for (pos, vel) in pos.iter_mut().zip(vel) {
+            pos.0 += vel.0;
+        }
+
+        seen.push((entities.to_vec(), tagged));
+    }
+
+    assert_eq!(seen, vec![(vec![a, b], false), (vec![c], true)]);
+    assert_eq!(Read::<Pos>::query().iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0, 0.0]);
+}
+
+#[test]
+fn optional_chunks() {
+    let mut world = World::default();
+
+    world.create((Pos(0.0),));
+    world.create((Pos(1.0), Vel(1.0)));
+
+    let chunks = <(Entity, TryRead<Vel>)>::query()
+        .iter_chunks(&world)
+        .map(|(_, (entities, vel))| (entities.len(), vel.map(|v| v.len())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(chunks, vec![(1, None), (1, Some(1))]);
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct Pos(pub u32);
+
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct Vel(pub u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Health(pub u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Name(pub &'static str);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Tag;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ChildOf;
+
+pub fn world() -> World {
+    let mut world = World::default();
+
+    world.register_clone::<Pos>();
+    world.register_clone::<Vel>();
+    world.register_clone::<Health>();
+    world.register_clone::<Name>();
+    world.register_clone::<Tag>();
+    world
+}
+
+pub fn spawn_movers(world: &mut World, count: u32) -> Vec<Entity> {
+    (0..count)
+        .map(|i| match i % 3 {
+            | 0 => world.create((Pos(i), Vel(1))),
+            | 1 => world.create((Pos(i), Vel(2), Tag)),
+            | _ => world.create((Pos(i),)),
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::query::{IntoQuery, QueryCursor, Read};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn resumes_across_archetypes() {
+    let mut world = World::default();
+    let query = Read::<Pos>::query();
+    let mut cursor = QueryCursor::new();
+
+    for i in 0..5 {
+        world.create((Pos(i),));
+    }
+
+    let first = query.iter_from_cursor(&world, &mut cursor, 3).map(|pos| pos.0).collect::<Vec<_>>();
+    assert_eq!(first, vec![0, 1, 2]);
+
+    for i in 5..7 {
+        world.create((Pos(i), Tag));
+    }
+
+    let second = query.iter_from_cursor(&world, &mut cursor, 3).map(|pos| pos.0).collect::<Vec<_>>();
+    assert_eq!(second, vec![3, 4, 5]);
+    assert_eq!(cursor.passes(), 0);
+
+    let third = query.iter_from_cursor(&world, &mut cursor, 3).map(|pos| pos.0).collect::<Vec<_>>();
+    assert_eq!(third, vec![6, 0, 1]);
+    assert_eq!(cursor.passes(), 1);
+
+    let fourth = query.iter_from_cursor(&world, &mut cursor, 2).map(|pos| pos.0).collect::<Vec<_>>();
+    assert_eq!(fourth, vec![2, 3]);
+    assert_eq!(cursor.position().1, 4);
+}
+
+#[test]
+fn remove_between_slices() {
+    let mut world = World::default();
+    let query = <(Entity, Read<Pos>)>::query();
+    let mut cursor = QueryCursor::new();
+    let entities = (0..6).map(|i| world.create((Pos(i),))).collect::<Vec<_>>();
+
+    let first = query.iter_from_cursor(&world, &mut cursor, 3).map(|(_, pos)| pos.0).collect::<Vec<_>>();
+    assert_eq!(first, vec![0, 1, 2]);
+
+    world.remove(entities[0]);
+
+    let mut rest = query.iter_from_cursor(&world, &mut cursor, 3).map(|(_, pos)| pos.0).collect::<Vec<_>>();
+    rest.sort();
+    assert_eq!(rest, vec![3, 4, 5]);
+    assert_eq!(cursor.passes(), 1);
+}
+
+#[test]
+fn remove_visited_between_slices() {
+    let mut world = World::default();
+    let query = Read::<Pos>::query();
+    let mut cursor = QueryCursor::new();
+    let entities = (0..6).map(|i| world.create((Pos(i),))).collect::<Vec<_>>();
+
+    query.iter_from_cursor(&world, &mut cursor, 4).for_each(drop);
+    world.remove(entities[1]);
+    world.remove(entities[2]);
+
+    let mut rest = query.iter_from_cursor(&world, &mut cursor, 6).map(|pos| pos.0).collect::<Vec<_>>();
+    rest.sort();
+    assert_eq!(rest, vec![0, 3, 4, 5]);
+    assert_eq!(cursor.passes(), 1);
+    assert_eq!(cursor.position().1, 4);
+}
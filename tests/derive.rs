@@ -0,0 +1,186 @@
+use atomic_refcell::AtomicRef;
+use ecs::archetype::ArchetypeDescriptor;
+use ecs::component::{Bundle, Component, ComponentId};
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::reflect::{Reflect, ReflectKind};
+use ecs::resource::Resources;
+use ecs::schedule::Schedule;
+use ecs::system::{ParamSystem, SystemParam, SystemQuery};
+use ecs::world::World;
+
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+struct Pos {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(f32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+#[component(storage = "null")]
+struct Frozen;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+#[component(storage = "single")]
+struct Camera;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Wrapper<T: Send + Sync + 'static>(T);
+
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+enum State {
+    Idle,
+    Moving {
+        speed: f32,
+        #[reflect(ignore)]
+        target: Option<u32>,
+    },
+}
+
+#[derive(Bundle)]
+struct Body {
+    pos: Pos,
+    vel: Vel,
+}
+
+#[derive(Bundle)]
+struct Unit {
+    body: Body,
+    frozen: Option<Frozen>,
+    state: State,
+}
+
+#[derive(Bundle)]
+struct Twice {
+    a: Vel,
+    b: Vel,
+}
+
+struct Gravity(f32);
+
+#[derive(SystemParam)]
+struct Physics<'world, 'resources> {
+    bodies: SystemQuery<'world, (Write<Pos>, Read<Vel>)>,
+    gravity: AtomicRef<'resources, Gravity>,
+}
+
+fn unit(frozen: bool) -> Unit {
+    Unit {
+        body: Body {
+            pos: Pos { x: 0.0, y: 0.0 },
+            vel: Vel(1.0),
+        },
+        frozen: frozen.then_some(Frozen),
+        state: State::Moving {
+            speed: 2.0,
+            target: Some(3),
+        },
+    }
+}
+
+#[test]
+fn component_storage_attribute() {
+    let mut world = World::default();
+
+    world.create((Frozen, 1u32));
+    world.create((Frozen, Wrapper("a")));
+    world.create((Camera,));
+
+    assert_eq!(Read::<Frozen>::query().iter(&world).count(), 2);
+    assert_eq!(Read::<u32>::query().iter(&world).copied().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(Read::<Wrapper<&str>>::query().iter(&world).next(), Some(&Wrapper("a")));
+    assert!(matches!(world.try_create((Camera,)), Err(EcsError::StorageFull { .. })));
+}
+
+#[test]
+fn bundle_nests_and_skips_missing_options() {
+    let mut world = World::default();
+    let a = world.create(unit(true));
+    let b = world.create(unit(false));
+
+    assert_eq!(
+        <(Entity, Read<Pos>, Read<Vel>, Read<State>)>::query()
+            .iter(&world)
+            .count(),
+        2
+    );
+    assert_eq!(
+        <(Entity, Read<Frozen>)>::query()
+            .iter(&world)
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>(),
+        vec![a]
+    );
+    assert!(world.contains(b));
+
+    let layout = Unit::layout();
+
+    assert_eq!(layout.components.len(), 3);
+    assert!(layout.contains(&[ComponentId::of::<Pos>(), ComponentId::of::<Vel>(), ComponentId::of::<State>()]));
+    assert!(matches!(
+        world.try_create(Twice {
+            a: Vel(1.0),
+            b: Vel(2.0)
+        }),
+        Err(EcsError::DuplicateComponent { .. })
+    ));
+}
+
+#[test]
+fn system_param_groups_queries_and_resources() {
+    let mut world = World::default();
+    let mut resources = Resources::default();
+    let a = world.create(unit(false));
+
+    resources.insert(Gravity(-1.0));
+    Schedule::new()
+        .with_system(ParamSystem::<Physics, _>::new(|mut physics: Physics| {
+            for (pos, vel) in physics.bodies.iter_mut() {
+                pos.x += vel.0;
+                pos.y += physics.gravity.0;
+            }
+        }))
+        .finish()
+        .run(&mut world, &mut resources);
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos { x: 1.0, y: -1.0 }));
+}
+
+#[test]
+fn reflect_structs_and_enums() {
+    let mut world = World::default();
+    let a = world.create(unit(false));
+
+    world.register_reflect::<Pos>();
+    world.register_reflect::<State>();
+
+    let mut entry = world.entry(a).unwrap();
+    let pos = entry.reflect(std::any::type_name::<Pos>()).unwrap();
+
+    assert_eq!(pos.kind(), ReflectKind::Struct);
+    assert_eq!(pos.fields(), &["x", "y"]);
+
+    entry
+        .reflect_mut(std::any::type_name::<Pos>())
+        .unwrap()
+        .path_mut("y")
+        .unwrap()
+        .set(5.0f32)
+        .unwrap();
+
+    let state = entry.reflect(std::any::type_name::<State>()).unwrap();
+
+    assert_eq!(state.kind(), ReflectKind::Enum);
+    assert_eq!(state.variant(), Some("Moving"));
+    assert_eq!(state.fields(), &["speed"]);
+    assert_eq!(state.path("speed").unwrap().downcast_ref::<f32>(), Some(&2.0));
+    assert!(state.field("target").is_none());
+    assert_eq!(entry.reflected().count(), 2);
+    drop(entry);
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos { x: 0.0, y: 5.0 }));
+    assert_eq!(State::Idle.variant(), Some("Idle"));
+}
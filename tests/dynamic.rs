@@ -0,0 +1,100 @@
+use ecs::component::{Component, ComponentDescriptor, ComponentId};
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read};
+use ecs::world::World;
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe fn count_drop(_: *mut u8) {
+    DROPPED.fetch_add(1, Ordering::SeqCst);
+}
+
+fn bytes(value: u32) -> [u8; 4] {
+    value.to_ne_bytes()
+}
+
+#[test]
+fn dynamic_components_round_trip() {
+    let mut world = World::default();
+    let health = world.register_dynamic(ComponentDescriptor::new("Health", Layout::new::<u32>()));
+    let armor = world.register_dynamic(ComponentDescriptor::new("Armor", Layout::new::<u32>()));
+    let a = unsafe { world.create_dynamic(&[(health, &bytes(10)), (armor, &bytes(2))]) };
+
+    assert!(health.is_dynamic());
+    assert_ne!(health, armor);
+
+    let mut entry = world.entry(a).unwrap();
+
+    assert_eq!(entry.dynamic(health), Some(&bytes(10)[..]));
+    unsafe { entry.dynamic_mut(armor) }.unwrap().copy_from_slice(&bytes(5));
+    entry.add_component(Pos(1));
+    assert!(entry.remove_dynamic(health));
+    assert!(!entry.remove_dynamic(health));
+    drop(entry);
+
+    let entry = world.entry(a).unwrap();
+
+    assert_eq!(entry.dynamic(health), None);
+    assert_eq!(entry.dynamic(armor), Some(&bytes(5)[..]));
+    assert_eq!(entry.components().len(), 2);
+    drop(entry);
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(1)));
+}
+
+#[test]
+fn dynamic_components_are_validated() {
+    let mut world = World::default();
+    let health = world.register_dynamic(ComponentDescriptor::new("Health", Layout::new::<u32>()));
+    let unknown = ComponentId::of::<Pos>();
+
+    assert!(matches!(
+        unsafe { world.try_create_dynamic(&[(health, &[1, 2])]) },
+        Err(EcsError::SizeMismatch { expected: 4, found: 2, .. })
+    ));
+    assert!(matches!(
+        unsafe { world.try_create_dynamic(&[(health, &bytes(1)), (health, &bytes(2))]) },
+        Err(EcsError::DuplicateComponent { .. })
+    ));
+    assert!(matches!(
+        unsafe { world.try_create_dynamic(&[(unknown, &bytes(1))]) },
+        Err(EcsError::UnregisteredComponent { .. })
+    ));
+
+    let a = world.create((Pos(0),));
+
+    assert!(matches!(
+        unsafe { world.entry(a).unwrap().try_add_dynamic(health, &[0; 8]) },
+        Err(EcsError::SizeMismatch { .. })
+    ));
+}
+
+#[test]
+fn dynamic_components_run_their_drop_fn() {
+    let mut world = World::default();
+    let handle = world.register_dynamic(ComponentDescriptor::new("Handle", Layout::new::<u32>()).with_drop(count_drop));
+    let a = unsafe { world.create_dynamic(&[(handle, &bytes(1))]) };
+    let b = unsafe { world.create_dynamic(&[(handle, &bytes(2))]) };
+
+    world.remove(a);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+
+    unsafe { world.entry(b).unwrap().add_dynamic(handle, &bytes(3)) };
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 2);
+
+    drop(world);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+#[should_panic(expected = "cannot be replicated as raw bytes")]
+fn dynamic_components_with_a_drop_fn_are_not_replicated() {
+    let mut world = World::default();
+    let handle = world.register_dynamic(ComponentDescriptor::new("Handle", Layout::new::<u32>()).with_drop(count_drop));
+
+    world.register_replicated_dynamic(handle);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::Pos;
+use ecs::component::{Component, ComponentDescriptor, ComponentId};
+use ecs::entity::Entity;
+use ecs::query::DynamicQuery;
+use ecs::world::World;
+use std::alloc::Layout;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Frozen;
+
+fn spawn(world: &mut World) -> (ComponentId, [Entity; 3]) {
+    let health = world.register_dynamic(ComponentDescriptor::new("Health", Layout::new::<u32>()));
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2), Frozen));
+    let c = world.create((Pos(3),));
+
+    unsafe { world.entry(a).unwrap().add_dynamic(health, &10u32.to_ne_bytes()) };
+    unsafe { world.entry(b).unwrap().add_dynamic(health, &20u32.to_ne_bytes()) };
+
+    (health, [a, b, c])
+}
+
+#[test]
+fn dynamic_query_filters_and_reads() {
+    let mut world = common::world();
+    let (health, [a, b, c]) = spawn(&mut world);
+    let pos = ComponentId::of::<Pos>();
+    let frozen = ComponentId::of::<Frozen>();
+
+    let query = DynamicQuery::new().read(pos).read(health).without(frozen);
+    let rows = query.iter(&world).collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].entity(), a);
+    assert_eq!(rows[0].get(0).unwrap().downcast_ref::<Pos>(), Some(&Pos(1)));
+    assert_eq!(rows[0].get(1).unwrap().bytes(), Some(&10u32.to_ne_bytes()[..]));
+    assert_eq!(rows[0].get(1).unwrap().downcast_ref::<Pos>(), None);
+
+    let query = DynamicQuery::new().read(pos).try_read(health).with(pos);
+    let mut optional = query
+        .iter(&world)
+        .map(|row| (row.entity(), row.get(1).is_some()))
+        .collect::<Vec<_>>();
+    optional.sort_by_key(|(e, _)| e.0);
+    assert_eq!(optional, vec![(a, true), (b, true), (c, false)]);
+
+    assert!(DynamicQuery::new().read(health).get(&world, c).is_none());
+    assert!(DynamicQuery::new().read(health).get(&world, b).is_some());
+}
+
+#[test]
+fn dynamic_query_writes() {
+    let mut world = common::world();
+    let (health, [a, b, _]) = spawn(&mut world);
+    let pos = ComponentId::of::<Pos>();
+    let query = DynamicQuery::new().write(pos).write(health);
+
+    assert!(!query.is_readonly());
+
+    for mut row in query.iter_mut(&mut world) {
+        row.get_mut(0).unwrap().downcast_mut::<Pos>().unwrap().0 += 100;
+        unsafe { row.get_mut(1).unwrap().bytes_mut() }.unwrap().copy_from_slice(&0u32.to_ne_bytes());
+    }
+
+    let read = DynamicQuery::new().read(pos).read(health);
+
+    for (entity, pos) in [(a, 101), (b, 102)] {
+        let row = read.get(&world, entity).unwrap();
+
+        assert_eq!(row.get(0).unwrap().downcast_ref::<Pos>(), Some(&Pos(pos)));
+        assert_eq!(row.get(1).unwrap().bytes(), Some(&0u32.to_ne_bytes()[..]));
+    }
+}
+
+#[test]
+#[should_panic(expected = "conflicting access")]
+fn dynamic_query_rejects_aliasing_terms() {
+    let pos = ComponentId::of::<Pos>();
+
+    DynamicQuery::new().read(pos).write(pos);
+}
+
+#[test]
+#[should_panic(expected = "requires `iter_mut`")]
+fn dynamic_query_write_needs_mutable_world() {
+    let mut world = common::world();
+    let (health, _) = spawn(&mut world);
+
+    DynamicQuery::new().write(health).iter(&world).for_each(drop);
+}
@@ -0,0 +1,25 @@
+use ecs::component::Component;
+use ecs::query::{IntoQuery, Read};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[test]
+fn removed_entities_are_freed() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2),));
+
+    assert!(world.remove(a));
+    assert!(!world.contains(a));
+    assert!(world.entry(a).is_none());
+    assert!(!world.remove(a));
+    assert!(world.contains(b));
+
+    world.create_with_id(a, (Pos(3),));
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(3)));
+    assert_eq!(Read::<Pos>::query().get(&world, b), Some(&Pos(2)));
+    assert_eq!(Read::<Pos>::query().iter(&world).count(), 2);
+}
@@ -0,0 +1,80 @@
+use ecs::component::Component;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::resource::Resources;
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(u32);
+
+struct Score(u32);
+
+#[test]
+fn world_and_entry_errors() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+
+    assert!(world.try_remove(a).is_ok());
+    assert_eq!(world.try_remove(a), Err(EcsError::NoSuchEntity(a)));
+    assert!(matches!(world.try_entry(a), Err(EcsError::NoSuchEntity(e)) if e == a));
+
+    let b = world.create((Pos(2),));
+    let mut entry = world.try_entry(b).unwrap();
+
+    assert!(matches!(entry.try_component::<Vel>(), Err(EcsError::MissingComponent { .. })));
+    assert!(matches!(entry.try_remove_component::<Vel>(), Err(EcsError::MissingComponent { .. })));
+    assert_eq!(entry.try_component::<Pos>(), Ok(&Pos(2)));
+    assert_eq!(entry.try_remove_component::<Pos>(), Ok(Pos(2)));
+}
+
+#[test]
+fn query_errors() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2), Vel(2)));
+
+    world.remove(b);
+
+    assert!(matches!(Read::<Vel>::query().try_get(&world, a), Err(EcsError::QueryMismatch { .. })));
+    assert_eq!(Read::<Pos>::query().try_get(&world, b), Err(EcsError::NoSuchEntity(b)));
+    assert_eq!(Write::<Pos>::query().try_get_mut(&mut world, a), Ok(&mut Pos(1)));
+}
+
+#[test]
+fn resource_errors() {
+    let mut resources = Resources::default();
+
+    assert!(matches!(resources.try_get::<Score>(), Err(EcsError::MissingResource { .. })));
+
+    resources.insert(Score(1));
+
+    let score = resources.get_mut::<Score>();
+
+    assert!(matches!(resources.try_get::<Score>(), Err(EcsError::ResourceBorrowed { .. })));
+    assert!(matches!(resources.try_get_mut::<Score>(), Err(EcsError::ResourceBorrowed { .. })));
+    drop(score);
+
+    assert_eq!(resources.try_get::<Score>().map(|s| s.0), Ok(1));
+}
+
+#[test]
+fn errors_describe_the_failure() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+    let message = world.try_entry(a).unwrap().try_component::<Vel>().unwrap_err().to_string();
+
+    assert!(message.contains("Vel"), "{}", message);
+    assert_eq!(
+        EcsError::MissingResource { resource: "Score" }.to_string(),
+        "resource `Score` not available"
+    );
+}
+
+#[test]
+#[should_panic(expected = "resource `error::Score` not available")]
+fn panicking_variants_report_the_error() {
+    Resources::default().get::<Score>();
+}
@@ -0,0 +1,55 @@
+use ecs::component::{Component, ComponentId};
+use ecs::entity::Entity;
+use ecs::query::{EntityRef, Has, IntoQuery, Read};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn has_reports_presence_without_filtering() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2), Tag));
+
+    let mut items = <(Entity, Read<Pos>, Has<Tag>)>::query()
+        .iter(&world)
+        .map(|(entity, pos, tagged)| (entity, pos.0, tagged))
+        .collect::<Vec<_>>();
+    items.sort_by_key(|&(_, pos, _)| pos);
+
+    assert_eq!(items, vec![(a, 1, false), (b, 2, true)]);
+    assert_eq!(Has::<Tag>::query().get(&world, a), Some(false));
+    assert_eq!(Has::<Tag>::query().iter(&world).count(), 2);
+}
+
+#[test]
+fn entity_ref_reads_any_component() {
+    let mut world = World::default();
+    let a = world.create((Pos(1), Vel(3)));
+    let b = world.create((Tag,));
+    let query = EntityRef::query();
+
+    let entity = query.get(&world, a).unwrap();
+    assert_eq!(entity.entity(), a);
+    assert_eq!(entity.get::<Pos>(), Some(&Pos(1)));
+    assert_eq!(entity.get::<Vel>(), Some(&Vel(3)));
+    assert_eq!(entity.get::<Tag>(), None);
+    assert!(entity.contains::<Vel>());
+    assert!(!entity.contains_dynamic(ComponentId::of::<Tag>()));
+    assert_eq!(entity.components().len(), 2);
+
+    let other = query.get(&world, b).unwrap();
+    assert!(other.contains::<Tag>());
+    assert!(other.get_dynamic(ComponentId::of::<Pos>()).is_none());
+
+    let mut entities = query.iter(&world).map(|entity| entity.entity()).collect::<Vec<_>>();
+    entities.sort_by_key(|entity| entity.0);
+    assert_eq!(entities, vec![a, b]);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{Health, Pos};
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::query::{IntoQuery, Read};
+use ecs::world::World;
+use std::sync::{Arc, Mutex};
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Dead(Entity);
+
+type Log = Arc<Mutex<Vec<(&'static str, u32)>>>;
+
+fn add_hooks(world: &mut World) -> Log {
+    let log = Log::default();
+    let (add, insert, remove) = (log.clone(), log.clone(), log.clone());
+
+    world
+        .register::<Health>()
+        .on_add(move |world, entity| add.lock().unwrap().push(("add", world.component::<Health>(entity).unwrap().0)))
+        .on_insert(move |world, entity| {
+            insert.lock().unwrap().push(("insert", world.component::<Health>(entity).unwrap().0))
+        })
+        .on_remove(move |world, entity| {
+            remove.lock().unwrap().push(("remove", world.component::<Health>(entity).unwrap().0));
+            world.defer(move |world| {
+                world.create((Dead(entity),));
+            });
+        });
+
+    log
+}
+
+#[test]
+fn hooks_run_on_spawn_replace_and_remove() {
+    let mut world = common::world();
+    let log = add_hooks(&mut world);
+    let a = world.create((Health(10), Pos(0)));
+
+    world.entry(a).unwrap().add_component(Health(5));
+    world.entry(a).unwrap().remove_component::<Health>();
+
+    assert_eq!(*log.lock().unwrap(), vec![("add", 10), ("insert", 10), ("insert", 5), ("remove", 5)]);
+    assert_eq!(Read::<Dead>::query().iter(&world).collect::<Vec<_>>(), vec![&Dead(a)]);
+}
+
+#[test]
+fn hooks_run_when_adding_to_an_existing_entity() {
+    let mut world = common::world();
+    let log = add_hooks(&mut world);
+    let a = world.create((Pos(0),));
+
+    world.entry(a).unwrap().add_component(Health(3));
+    world.remove(a);
+
+    assert_eq!(*log.lock().unwrap(), vec![("add", 3), ("insert", 3), ("remove", 3)]);
+    assert_eq!(Read::<Dead>::query().iter(&world).collect::<Vec<_>>(), vec![&Dead(a)]);
+    assert!(!world.contains(a));
+}
+
+#[test]
+fn components_without_hooks_are_untouched() {
+    let mut world = common::world();
+    let log = add_hooks(&mut world);
+    let a = world.create((Pos(0),));
+
+    world.entry(a).unwrap().add_component(Pos(1));
+    world.remove(a);
+
+    assert!(log.lock().unwrap().is_empty());
+    assert_eq!(Read::<Dead>::query().iter(&world).count(), 0);
+}
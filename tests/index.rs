@@ -0,0 +1,108 @@
+mod common;
+
+use common::Name;
+use ecs::component::Component;
+use ecs::error::EcsError;
+use ecs::index::{IndexRef, RangeIndex, UniqueIndex};
+use ecs::query::{IntoQuery, Write};
+use ecs::resource::Resources;
+use ecs::schedule::Schedule;
+use ecs::system::{ParamSystem, SystemParam};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Level(u32);
+
+type ByName = UniqueIndex<Name, &'static str>;
+type ByLevel = RangeIndex<Level, u32>;
+
+#[derive(SystemParam)]
+struct Lookup<'world> {
+    names: IndexRef<'world, ByName>,
+    again: IndexRef<'world, ByName>,
+    levels: IndexRef<'world, ByLevel>,
+}
+
+fn add_indexes(world: &mut World) {
+    world.add_index(ByName::new(|name: &Name| name.0));
+    world.add_index(ByLevel::new(|level: &Level| level.0));
+}
+
+#[test]
+fn nested_index_refs_do_not_deadlock() {
+    let mut world = common::world();
+
+    add_indexes(&mut world);
+
+    let a = world.create((Name("a"), Level(1)));
+
+    let outer = world.index::<ByName>();
+    let inner = world.index::<ByName>();
+
+    assert_eq!(outer.get(&"a"), Some(a));
+    assert_eq!(inner.get(&"a"), Some(a));
+}
+
+#[test]
+fn missing_index_is_an_error() {
+    let world = common::world();
+
+    assert!(matches!(world.try_index::<ByName>(), Err(EcsError::MissingIndex { .. })));
+}
+
+#[test]
+fn system_reads_the_same_index_twice() {
+    let mut world = common::world();
+    let mut resources = Resources::default();
+
+    add_indexes(&mut world);
+
+    let a = world.create((Name("a"), Level(1)));
+    let b = world.create((Name("b"), Level(3)));
+
+    Schedule::new()
+        .with_system(ParamSystem::<Lookup, _>::new(move |lookup: Lookup| {
+            assert_eq!(lookup.names.get(&"a"), Some(a));
+            assert_eq!(lookup.again.get(&"b"), Some(b));
+            assert_eq!(lookup.levels.range(2..).map(|(_, e)| e).collect::<Vec<_>>(), vec![b]);
+        }))
+        .finish()
+        .run(&mut world, &mut resources);
+}
+
+#[test]
+fn unique_index_reports_collisions() {
+    let mut world = common::world();
+
+    add_indexes(&mut world);
+
+    let a = world.create((Name("a"),));
+    let b = world.create((Name("a"),));
+    let c = world.create((Name("c"),));
+
+    {
+        let names = world.index::<ByName>();
+
+        assert_eq!(names.get(&"a"), Some(a));
+        assert!(names.has_collisions());
+        assert_eq!(names.collisions().collect::<Vec<_>>(), vec![(&"a", &[b][..])]);
+    }
+
+    world.remove(a);
+
+    {
+        let names = world.index::<ByName>();
+
+        assert_eq!(names.get(&"a"), Some(b));
+        assert!(!names.has_collisions());
+    }
+
+    Write::<Name>::query().get_mut(&mut world, c).unwrap().0 = "a";
+
+    let names = world.index::<ByName>();
+
+    assert_eq!(names.get(&"a"), Some(b));
+    assert_eq!(names.get(&"c"), None);
+    assert_eq!(names.collisions().collect::<Vec<_>>(), vec![(&"a", &[c][..])]);
+    assert_eq!(names.len(), 2);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{Pos, Vel};
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::resource::Resources;
+use ecs::schedule::Schedule;
+use ecs::system::{QuerySet, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[test]
+fn par_for_each_visits_every_entity_once() {
+    let mut world = common::world();
+    let sum = AtomicU64::new(0);
+    let count = AtomicU64::new(0);
+
+    common::spawn_movers(&mut world, 1000);
+    Read::<Pos>::query().par_for_each(&world, 7, |pos| {
+        sum.fetch_add(u64::from(pos.0), Ordering::Relaxed);
+        count.fetch_add(1, Ordering::Relaxed);
+    });
+
+    assert_eq!(count.into_inner(), 1000);
+    assert_eq!(sum.into_inner(), (0..1000).sum::<u64>());
+}
+
+#[test]
+fn par_for_each_mut_writes_every_entity() {
+    let mut world = common::world();
+
+    common::spawn_movers(&mut world, 1000);
+    <(Write<Pos>, Read<Vel>)>::query().par_for_each_mut(&mut world, 16, |(pos, vel)| pos.0 += vel.0 * 1000);
+
+    let moved = Read::<Pos>::query().iter(&world).filter(|pos| pos.0 >= 1000).count();
+
+    assert_eq!(moved, <(Read<Pos>, Read<Vel>)>::query().iter(&world).count());
+}
+
+struct Integrate;
+
+impl System for Integrate {
+    type Resources = ();
+    type Queries = ((Write<Pos>, Read<Vel>),);
+
+    fn run(&mut self, (mut query,): <Self::Queries as QuerySet>::Result, _: ()) {
+        query.par_for_each_mut(32, |(pos, vel)| pos.0 += vel.0);
+    }
+}
+
+#[test]
+fn par_for_each_in_a_system() {
+    let mut world = common::world();
+    let mut resources = Resources::default();
+
+    common::spawn_movers(&mut world, 1000);
+
+    let before = Read::<Pos>::query().iter(&world).map(|pos| pos.0).sum::<u32>();
+
+    Schedule::new()
+        .with_system(Integrate)
+        .finish()
+        .run(&mut world, &mut resources);
+
+    let after = Read::<Pos>::query().iter(&world).map(|pos| pos.0).sum::<u32>();
+
+    assert_eq!(after - before, Read::<Vel>::query().iter(&world).map(|vel| vel.0).sum::<u32>());
+}
+
+#[test]
+#[should_panic(expected = "batch size must be greater than zero")]
+fn par_for_each_rejects_empty_batches() {
+    Read::<Pos>::query().par_for_each(&common::world(), 0, |_| {});
+}
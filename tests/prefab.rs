@@ -0,0 +1,113 @@
+mod common;
+
+use common::{ChildOf, Health, Name};
+use ecs::component::{Component, ComponentId};
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::prefab::Prefab;
+use ecs::query::{IntoQuery, Read};
+use ecs::relation::Relations;
+
+#[derive(Component, Debug, PartialEq)]
+struct Handle(u32);
+
+#[test]
+fn clone_entity_copies_components() {
+    let mut world = common::world();
+    let a = world.create((Name("orc"), Health(10)));
+    let b = world.clone_entity(a).unwrap();
+
+    assert_ne!(a, b);
+    assert_eq!(Read::<Name>::query().get(&world, b), Some(&Name("orc")));
+    assert_eq!(Read::<Health>::query().get(&world, b), Some(&Health(10)));
+
+    let c = world.create((Name("lamp"), Handle(1)));
+
+    assert!(matches!(world.clone_entity(c), Err(EcsError::NotCloneable { .. })));
+    assert_eq!(Read::<Name>::query().iter(&world).count(), 3);
+}
+
+#[test]
+fn instantiate_with_overrides() {
+    let mut world = common::world();
+    let prefab = Prefab::new().with(Name("orc")).with(Health(10)).with(Health(20));
+
+    assert!(prefab.contains(ComponentId::of::<Health>()));
+    assert_eq!(prefab.get::<Health>(), Some(&Health(20)));
+    assert_eq!(prefab.components().count(), 2);
+
+    let a = world.instantiate(&prefab).unwrap();
+    let b = world.instantiate_with(&prefab, (Health(1), Handle(7))).unwrap();
+    let batch = world
+        .instantiate_batch(&prefab, vec![(Health(2),), (Health(3),)])
+        .unwrap();
+
+    assert_eq!(Read::<Health>::query().get(&world, a), Some(&Health(20)));
+    assert_eq!(Read::<Health>::query().get(&world, b), Some(&Health(1)));
+    assert_eq!(Read::<Handle>::query().get(&world, b), Some(&Handle(7)));
+    assert_eq!(
+        batch.iter().map(|&e| Read::<Health>::query().get(&world, e).unwrap().0).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+    assert_eq!(Read::<Name>::query().iter(&world).count(), 4);
+}
+
+#[test]
+fn prefabs_capture_and_spawn_children() {
+    let mut world = common::world();
+    let root = world.create((Name("ship"),));
+    let turret = world.create((Name("turret"), Health(5)));
+
+    world.add_relation(turret, ChildOf, root);
+
+    let prefab = world.prefab_with_children::<ChildOf>(root).unwrap();
+
+    assert_eq!(prefab.children().count(), 1);
+
+    let copies = world.instantiate_batch(&prefab, vec![(), ()]).unwrap();
+
+    for copy in copies {
+        let children = world.sources::<ChildOf>(copy).collect::<Vec<_>>();
+
+        assert_eq!(children.len(), 1);
+        assert_eq!(Read::<Name>::query().get(&world, children[0]), Some(&Name("turret")));
+        assert_eq!(Read::<Health>::query().get(&world, children[0]), Some(&Health(5)));
+    }
+
+    assert_eq!(<(Entity, Read<Name>)>::query().iter(&world).count(), 6);
+}
+
+#[test]
+fn cloned_relations_are_linked() {
+    let mut world = common::world();
+    let root = world.create((Name("ship"),));
+    let turret = world.create((Name("turret"),));
+
+    world.register_clone::<Relations<ChildOf>>();
+    world.add_relation(turret, ChildOf, root);
+
+    let copy = world.clone_entity(turret).unwrap();
+    let mut sources = world.sources::<ChildOf>(root).collect::<Vec<_>>();
+
+    sources.sort_by_key(|e| e.0);
+    assert_eq!(sources, vec![turret, copy]);
+
+    world.remove(root);
+    assert!(world.relations::<ChildOf>(copy).is_none());
+}
+
+#[test]
+fn relation_cycles_are_an_error() {
+    let mut world = common::world();
+    let a = world.create((Name("a"),));
+    let b = world.create((Name("b"),));
+
+    world.register_clone::<Relations<ChildOf>>();
+    world.add_relation(a, ChildOf, b);
+    world.add_relation(b, ChildOf, a);
+
+    assert!(matches!(
+        world.prefab_with_children::<ChildOf>(a),
+        Err(EcsError::RelationCycle { entity, .. }) if entity == a
+    ));
+}
@@ -0,0 +1,126 @@
+use ecs::component::Component;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn count_sees_new_archetypes() {
+    let mut world = World::default();
+    let query = Read::<Pos>::query();
+
+    world.create((Pos(0),));
+    world.create((Pos(1),));
+    world.create((Pos(2),));
+    assert_eq!(query.count(&world), 3);
+
+    world.create((Pos(3), Tag));
+    assert_eq!(query.count(&world), 4);
+    assert_eq!(query.iter(&world).count(), 4);
+    assert_eq!(query.iter_chunks(&world).map(|(entities, _)| entities.len()).sum::<usize>(), 4);
+}
+
+#[test]
+fn single_sees_new_archetypes() {
+    let mut world = World::default();
+    let query = Read::<Tag>::query();
+
+    world.create((Pos(0),));
+    assert!(query.single(&world).is_err());
+
+    world.create((Pos(1), Tag));
+    assert!(query.single(&world).is_ok());
+}
+
+#[test]
+fn cloned_query_keeps_scanning() {
+    let mut world = World::default();
+    let query = Write::<Pos>::query();
+
+    world.create((Pos(0),));
+    assert_eq!(query.count(&world), 1);
+
+    let cloned = query.clone();
+    world.create((Pos(1), Tag));
+    assert_eq!(cloned.count(&world), 2);
+
+    for pos in cloned.iter_mut(&mut world) {
+        pos.0 += 10;
+    }
+
+    let mut values = Read::<Pos>::query().iter(&world).map(|pos| pos.0).collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, vec![10, 11]);
+}
+
+#[test]
+fn iterators_are_exact_size_and_double_ended() {
+    let mut world = World::default();
+
+    for i in 0..3 {
+        world.create((Pos(i),));
+    }
+
+    for i in 3..5 {
+        world.create((Pos(i), Tag));
+    }
+
+    let query = Read::<Pos>::query();
+    let mut iter = query.iter(&world);
+
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next().map(|pos| pos.0), Some(0));
+    assert_eq!(iter.next_back().map(|pos| pos.0), Some(4));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    assert_eq!(query.iter(&world).rev().map(|pos| pos.0).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    assert_eq!(query.iter(&world).nth(3).map(|pos| pos.0), Some(3));
+    assert_eq!(query.count(&world), 5);
+    assert_eq!(Read::<Tag>::query().count(&world), 2);
+
+    let mut mixed = query.iter(&world);
+    let back = (0..4).filter_map(|_| mixed.next_back()).map(|pos| pos.0).collect::<Vec<_>>();
+
+    assert_eq!(back, vec![4, 3, 2, 1]);
+    assert_eq!(mixed.next().map(|pos| pos.0), Some(0));
+    assert!(mixed.next().is_none());
+    assert!(mixed.next_back().is_none());
+}
+
+#[test]
+fn get_many_mut_and_single() {
+    let mut world = World::default();
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2), Tag));
+    let query = Write::<Pos>::query();
+
+    {
+        let [x, y] = query.get_many_mut(&mut world, [a, b]).unwrap();
+        std::mem::swap(x, y);
+    }
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(2)));
+    assert_eq!(query.try_get_many_mut(&mut world, [a, a]).err(), Some(EcsError::DuplicateEntity(a)));
+    assert!(matches!(
+        <(Write<Pos>, Read<Tag>)>::query().try_get_many_mut(&mut world, [a]),
+        Err(EcsError::QueryMismatch { .. })
+    ));
+
+    assert_eq!(<(Read<Pos>, Read<Tag>)>::query().single(&world).map(|(pos, _)| pos.0), Ok(1));
+    assert!(matches!(
+        Read::<Pos>::query().single(&world),
+        Err(EcsError::MultipleMatchingEntities { count: 2, .. })
+    ));
+
+    world.remove(b);
+    query.single_mut(&mut world).unwrap().0 = 7;
+    world.remove(a);
+
+    assert!(matches!(Read::<Pos>::query().single(&world), Err(EcsError::NoMatchingEntity { .. })));
+}
@@ -0,0 +1,77 @@
+use ecs::component::Component;
+use ecs::query::{IntoQuery, Read};
+use ecs::reflect::{Reflect, ReflectKind};
+use ecs::world::World;
+use std::any::type_name;
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+struct Stats {
+    hp: u32,
+    name: String,
+}
+
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+struct Unit {
+    stats: Stats,
+    target: Option<u32>,
+}
+
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+struct Pos(f32, f32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Hidden(u32);
+
+fn unit() -> Unit {
+    Unit {
+        stats: Stats {
+            hp: 10,
+            name: "knight".to_string(),
+        },
+        target: Some(4),
+    }
+}
+
+#[test]
+fn reflect_paths_through_nested_values() {
+    let mut unit = unit();
+    let value = &mut unit as &mut dyn Reflect;
+
+    assert_eq!(value.type_name(), type_name::<Unit>());
+    assert_eq!(value.fields(), &["stats", "target"]);
+    assert_eq!(value.path("stats.hp").unwrap().downcast_ref::<u32>(), Some(&10));
+    assert_eq!(value.path("target.0").unwrap().downcast_ref::<u32>(), Some(&4));
+    assert_eq!(value.path("target").unwrap().variant(), Some("Some"));
+    assert_eq!(value.path("stats.hp").unwrap().kind(), ReflectKind::Value);
+    assert!(value.path("stats.missing").is_none());
+
+    assert!(value.path_mut("stats.name").unwrap().set("paladin".to_string()).is_ok());
+    assert_eq!(value.path_mut("stats.hp").unwrap().set(1.0f32), Err(1.0));
+    value.path_mut("target").unwrap().set(None::<u32>).unwrap();
+
+    assert_eq!(unit.stats.name, "paladin");
+    assert_eq!(unit.stats.hp, 10);
+    assert_eq!(unit.target, None);
+}
+
+#[test]
+fn reflect_components_by_type_name() {
+    let mut world = World::default();
+    let a = world.create((unit(), Pos(1.0, 2.0), Hidden(0)));
+
+    world.register_reflect::<Unit>();
+    world.register_reflect::<Pos>();
+
+    let mut entry = world.entry(a).unwrap();
+
+    assert_eq!(entry.reflected().count(), 2);
+    assert!(entry.reflect(type_name::<Hidden>()).is_none());
+    assert_eq!(entry.reflect(type_name::<Pos>()).unwrap().fields(), &["0", "1"]);
+
+    entry.reflect_mut(type_name::<Pos>()).unwrap().field_mut("1").unwrap().set(5.0f32).unwrap();
+    entry.reflect_mut(type_name::<Unit>()).unwrap().path_mut("stats.hp").unwrap().set(3u32).unwrap();
+    drop(entry);
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(1.0, 5.0)));
+    assert_eq!(Read::<Unit>::query().get(&world, a).map(|u| u.stats.hp), Some(3));
+}
@@ -0,0 +1,98 @@
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::query::{IntoQuery, Read, Related, Target};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Name(&'static str);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Likes(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct OwnedBy;
+
+#[test]
+fn relations_are_keyed_by_kind_and_target() {
+    let mut world = World::default();
+    let alice = world.create((Name("alice"),));
+    let bob = world.create((Name("bob"),));
+    let carol = world.create((Name("carol"),));
+
+    assert!(world.add_relation(alice, Likes(1), bob));
+    assert!(world.add_relation(alice, Likes(2), carol));
+    assert!(world.add_relation(carol, Likes(3), bob));
+    assert!(world.add_relation(bob, OwnedBy, alice));
+
+    let likes = world.relations::<Likes>(alice).unwrap();
+
+    assert_eq!(likes.len(), 2);
+    assert_eq!(likes.get(carol), Some(&Likes(2)));
+    assert!(world.relations::<OwnedBy>(alice).is_none());
+
+    let mut fans = world.sources::<Likes>(bob).collect::<Vec<_>>();
+    fans.sort_by_key(|e| e.0);
+    assert_eq!(fans, vec![alice, carol]);
+
+    assert_eq!(world.remove_relation::<Likes>(alice, bob), Some(Likes(1)));
+    assert_eq!(world.sources::<Likes>(bob).collect::<Vec<_>>(), vec![carol]);
+}
+
+#[test]
+fn relation_queries() {
+    let mut world = World::default();
+    let alice = world.create((Name("alice"),));
+    let bob = world.create((Name("bob"),));
+    let sword = world.create((Name("sword"),));
+    let shield = world.create((Name("shield"),));
+
+    world.add_relation(sword, OwnedBy, alice);
+    world.add_relation(shield, OwnedBy, bob);
+    world.add_relation(alice, Likes(1), bob);
+
+    let mut owners = <(Read<Name>, Target<OwnedBy, Name>)>::query()
+        .iter(&world)
+        .map(|(item, owner)| (item.0, owner.map(|n| n.0)))
+        .collect::<Vec<_>>();
+    owners.sort();
+    assert_eq!(owners, vec![("shield", Some("bob")), ("sword", Some("alice"))]);
+
+    let likes_bob = <(Entity, Related<Likes>)>::query()
+        .iter(&world)
+        .filter(|(_, likes)| likes.contains(bob))
+        .map(|(e, _)| e)
+        .collect::<Vec<_>>();
+    assert_eq!(likes_bob, vec![alice]);
+}
+
+#[test]
+fn relations_are_removed_with_their_target() {
+    let mut world = World::default();
+    let alice = world.create((Name("alice"),));
+    let bob = world.create((Name("bob"),));
+    let carol = world.create((Name("carol"),));
+
+    world.add_relation(alice, Likes(1), bob);
+    world.add_relation(alice, Likes(2), carol);
+    world.add_relation(carol, OwnedBy, bob);
+    world.remove(bob);
+
+    assert_eq!(world.relations::<Likes>(alice).map(|r| r.targets().collect::<Vec<_>>()), Some(vec![carol]));
+    assert!(world.relations::<OwnedBy>(carol).is_none());
+    assert_eq!(Related::<OwnedBy>::query().iter(&world).count(), 0);
+    assert!(!world.add_relation(alice, Likes(3), bob));
+}
+
+#[test]
+fn target_resolves_only_the_first_target() {
+    let mut world = World::default();
+    let alice = world.create((Name("alice"),));
+    let bob = world.create((Name("bob"),));
+    let carol = world.create((Name("carol"),));
+
+    world.add_relation(alice, Likes(1), bob);
+    world.add_relation(alice, Likes(2), carol);
+
+    assert_eq!(Target::<Likes, Name>::query().get(&world, alice), Some(Some(&Name("bob"))));
+    assert_eq!(Related::<Likes>::query().get(&world, alice).map(|r| r.targets().count()), Some(2));
+}
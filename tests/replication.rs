@@ -0,0 +1,131 @@
+use ecs::component::{Component, ComponentId};
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::replication::{DeltaDecoder, DeltaEncoder, Replicate};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Secret(u32);
+
+impl Replicate for Pos {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out)
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        u32::decode(bytes).map(Pos)
+    }
+}
+
+fn worlds() -> (World, World) {
+    let mut server = World::default();
+    let mut client = World::default();
+
+    server.register_replicated::<Pos>();
+    client.register_replicated::<Pos>();
+    server.register_replicated::<u32>();
+    client.register_replicated::<u32>();
+
+    (server, client)
+}
+
+fn replicated(client: &World, decoder: &DeltaDecoder, remote: Entity) -> Option<(Option<Pos>, Option<u32>)> {
+    let local = decoder.get(remote)?;
+
+    if !client.contains(local) {
+        return None;
+    }
+
+    Some((
+        Read::<Pos>::query().get(client, local).cloned(),
+        Read::<u32>::query().get(client, local).copied(),
+    ))
+}
+
+#[test]
+fn deltas_replicate_spawns_changes_and_despawns() {
+    let (mut server, mut client) = worlds();
+    let mut encoder = DeltaEncoder::new();
+    let mut decoder = DeltaDecoder::new();
+    let a = server.create((Pos(1), 7u32, Secret(9)));
+    let b = server.create((Pos(2),));
+
+    decoder.apply(&mut client, &encoder.encode(&server)).unwrap();
+
+    assert_eq!(replicated(&client, &decoder, a), Some((Some(Pos(1)), Some(7))));
+    assert_eq!(replicated(&client, &decoder, b), Some((Some(Pos(2)), None)));
+    assert_eq!(Read::<Secret>::query().iter(&client).count(), 0);
+
+    Write::<Pos>::query().get_mut(&mut server, b).unwrap().0 = 5;
+    server.entry(a).unwrap().remove_component::<u32>();
+    let c = server.create((3u32,));
+    server.remove(b);
+
+    decoder.apply(&mut client, &encoder.encode(&server)).unwrap();
+
+    assert_eq!(replicated(&client, &decoder, a), Some((Some(Pos(1)), None)));
+    assert_eq!(replicated(&client, &decoder, b), None);
+    assert_eq!(replicated(&client, &decoder, c), Some((None, Some(3))));
+    assert_eq!(decoder.entities().count(), 2);
+}
+
+#[test]
+fn unchanged_worlds_send_no_values() {
+    let (mut server, mut client) = worlds();
+    let mut encoder = DeltaEncoder::new();
+    let mut decoder = DeltaDecoder::new();
+
+    for i in 0..10 {
+        server.create((Pos(i), i));
+    }
+
+    let full = encoder.encode(&server);
+    let empty = encoder.encode(&server);
+
+    assert!(empty.len() < full.len() / 4, "{} vs {}", empty.len(), full.len());
+
+    decoder.apply(&mut client, &full).unwrap();
+    decoder.apply(&mut client, &empty).unwrap();
+    assert_eq!(Read::<Pos>::query().iter(&client).count(), 10);
+}
+
+#[test]
+fn malformed_or_unknown_deltas_are_errors() {
+    let (mut server, _) = worlds();
+    let mut encoder = DeltaEncoder::new();
+
+    server.create((Pos(1),));
+
+    let bytes = encoder.encode(&server);
+
+    assert!(matches!(
+        DeltaDecoder::new().apply(&mut World::default(), &bytes),
+        Err(EcsError::NotReplicated { .. })
+    ));
+    assert!(matches!(
+        DeltaDecoder::new().apply(&mut worlds().1, &bytes[..bytes.len() - 1]),
+        Err(EcsError::MalformedDelta { .. })
+    ));
+}
+
+#[test]
+fn failed_deltas_leave_the_world_untouched() {
+    let (mut server, _) = worlds();
+    let mut client = World::default();
+    let mut encoder = DeltaEncoder::new();
+
+    client.register_replicated::<Pos>();
+    server.create((Pos(1),));
+    server.create((Pos(2), 3u32));
+
+    assert_eq!(server.registry().replication_id(ComponentId::of::<u32>()), Some(1));
+    assert!(matches!(
+        DeltaDecoder::new().apply(&mut client, &encoder.encode(&server)),
+        Err(EcsError::NotReplicated { .. })
+    ));
+    assert_eq!(Read::<Pos>::query().iter(&client).count(), 0);
+}
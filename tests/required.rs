@@ -0,0 +1,62 @@
+mod common;
+
+use common::{Health, Pos};
+use ecs::component::Component;
+use ecs::query::{IntoQuery, Read};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Player;
+
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+struct Transform(u32);
+
+fn add_requirements(world: &mut World) {
+    world.register::<Player>().require::<Pos>().require_with(|| Health(100));
+    world.register::<Pos>().require::<Transform>();
+}
+
+#[test]
+fn required_components_are_added_on_spawn() {
+    let mut world = common::world();
+
+    add_requirements(&mut world);
+
+    let a = world.create((Player,));
+    let b = world.create((Player, Pos(3)));
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(0)));
+    assert_eq!(Read::<Health>::query().get(&world, a), Some(&Health(100)));
+    assert_eq!(Read::<Transform>::query().get(&world, a), Some(&Transform(0)));
+    assert_eq!(Read::<Pos>::query().get(&world, b), Some(&Pos(3)));
+    assert_eq!(Read::<Transform>::query().get(&world, b), Some(&Transform(0)));
+}
+
+#[test]
+fn required_components_are_added_on_insert() {
+    let mut world = common::world();
+
+    add_requirements(&mut world);
+
+    let a = world.create((Health(5),));
+
+    world.entry(a).unwrap().add_component(Player);
+
+    assert_eq!(Read::<Health>::query().get(&world, a), Some(&Health(5)));
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(0)));
+    assert_eq!(Read::<Transform>::query().get(&world, a), Some(&Transform(0)));
+}
+
+#[test]
+fn cyclic_requirements_terminate() {
+    let mut world = common::world();
+
+    add_requirements(&mut world);
+    world.register::<Transform>().require::<Pos>();
+
+    let a = world.create((Transform(7),));
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(0)));
+    assert_eq!(Read::<Transform>::query().get(&world, a), Some(&Transform(7)));
+    assert_eq!(<(Read<Pos>, Read<Transform>)>::query().iter(&world).count(), 1);
+}
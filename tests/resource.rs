@@ -0,0 +1,184 @@
+use ecs::error::EcsError;
+use ecs::resource::{ReadScoped, ResourceSet, Resources, ScopedResource, WriteScoped};
+use ecs::schedule::Schedule;
+use ecs::system::{QuerySet, System};
+use ecs::world::World;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+struct Encoder<'a> {
+    commands: &'a mut Vec<String>,
+}
+
+struct FrameEncoder;
+
+impl ScopedResource for FrameEncoder {
+    type Target<'a> = Encoder<'a>;
+}
+
+struct Draw(&'static str);
+
+impl System for Draw {
+    type Resources = WriteScoped<FrameEncoder>;
+    type Queries = ();
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, mut encoder: <Self::Resources as ResourceSet>::Result) {
+        encoder.with_mut(|encoder| encoder.commands.push(self.0.to_string()));
+    }
+}
+
+struct Count(usize);
+
+impl System for Count {
+    type Resources = ReadScoped<FrameEncoder>;
+    type Queries = ();
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, encoder: <Self::Resources as ResourceSet>::Result) {
+        self.0 = encoder.with(|encoder| encoder.commands.len());
+    }
+}
+
+#[test]
+fn scope_injects_a_borrowed_resource() {
+    let mut world = World::default();
+    let mut resources = Resources::default();
+    let mut commands = Vec::new();
+    let mut encoder = Encoder { commands: &mut commands };
+    let mut schedule = Schedule::new()
+        .with_system(Draw("clear"))
+        .with_system(Draw("triangle"))
+        .finish();
+
+    resources.scope::<FrameEncoder, _, _>(&mut encoder, |resources| {
+        assert!(resources.contains_scoped::<FrameEncoder>());
+        schedule.run(&mut world, resources);
+    });
+
+    assert!(!resources.contains_scoped::<FrameEncoder>());
+    assert_eq!(commands, vec!["clear", "triangle"]);
+}
+
+#[test]
+fn scoped_resource_is_removed_on_panic() {
+    let mut resources = Resources::default();
+    let mut commands = Vec::new();
+    let mut encoder = Encoder { commands: &mut commands };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        resources.scope::<FrameEncoder, _, _>(&mut encoder, |resources| {
+            resources.with_scoped_mut::<FrameEncoder, _, _>(|encoder| encoder.commands.push("before".to_string()));
+            panic!("system failed");
+        })
+    }));
+
+    assert!(result.is_err());
+    assert!(!resources.contains_scoped::<FrameEncoder>());
+    assert!(matches!(
+        resources.try_with_scoped::<FrameEncoder, _, _>(|_| ()),
+        Err(EcsError::MissingResource { .. })
+    ));
+    assert_eq!(commands, vec!["before"]);
+}
+
+#[test]
+fn nested_scopes_restore_the_outer_resource() {
+    let mut resources = Resources::default();
+    let mut outer = Vec::new();
+    let mut inner = Vec::new();
+    let mut outer_encoder = Encoder { commands: &mut outer };
+    let mut inner_encoder = Encoder { commands: &mut inner };
+
+    resources.scope::<FrameEncoder, _, _>(&mut outer_encoder, |resources| {
+        resources.scope::<FrameEncoder, _, _>(&mut inner_encoder, |resources| {
+            resources.with_scoped_mut::<FrameEncoder, _, _>(|encoder| encoder.commands.push("inner".to_string()));
+        });
+        resources.with_scoped_mut::<FrameEncoder, _, _>(|encoder| encoder.commands.push("outer".to_string()));
+    });
+
+    assert_eq!(outer, vec!["outer"]);
+    assert_eq!(inner, vec!["inner"]);
+}
+
+#[test]
+fn swapped_out_scoped_resource_is_not_accessible() {
+    let mut resources = Resources::default();
+    let mut taken = Resources::default();
+    let mut commands = Vec::new();
+    let mut encoder = Encoder { commands: &mut commands };
+
+    resources.scope::<FrameEncoder, _, _>(&mut encoder, |resources| std::mem::swap(resources, &mut taken));
+
+    assert!(!resources.contains_scoped::<FrameEncoder>());
+    assert!(matches!(
+        taken.try_with_scoped::<FrameEncoder, _, _>(|_| ()),
+        Err(EcsError::MissingResource { .. })
+    ));
+}
+
+#[test]
+fn scoped_resource_access_errors() {
+    let mut resources = Resources::default();
+    let mut commands = vec!["a".to_string()];
+    let mut encoder = Encoder { commands: &mut commands };
+    let mut count = Count(0);
+
+    resources.scope::<FrameEncoder, _, _>(&mut encoder, |resources| {
+        let borrowed = resources.with_scoped::<FrameEncoder, _, _>(|_| {
+            resources.try_with_scoped_mut::<FrameEncoder, _, _>(|_| ())
+        });
+        assert!(matches!(borrowed, Err(EcsError::ResourceBorrowed { .. })));
+
+        let shared = resources.with_scoped::<FrameEncoder, _, _>(|_| {
+            resources.try_with_scoped::<FrameEncoder, _, _>(|encoder| encoder.commands.len())
+        });
+        assert_eq!(shared, Ok(1));
+
+        let resources = &*resources;
+        let other_thread = std::thread::scope(|scope| {
+            scope
+                .spawn(|| resources.try_with_scoped::<FrameEncoder, _, _>(|_| ()))
+                .join()
+                .unwrap()
+        });
+        assert!(matches!(other_thread, Err(EcsError::NonSendAccess { .. })));
+
+        count.run((), <ReadScoped<FrameEncoder> as ResourceSet>::fetch(resources));
+    });
+
+    assert_eq!(count.0, 1);
+    assert!(Count(0).is_thread_local());
+}
+
+#[test]
+fn non_send_resources_stay_on_the_owner_thread() {
+    let mut resources = Resources::default();
+
+    resources.insert_non_send(Rc::new(1u32));
+    *resources.get_non_send_mut::<Rc<u32>>() = Rc::new(2);
+    assert_eq!(**resources.get_non_send::<Rc<u32>>(), 2);
+    assert!(matches!(
+        resources.try_get_non_send::<Rc<u64>>(),
+        Err(EcsError::MissingResource { .. })
+    ));
+
+    {
+        let _borrow = resources.get_non_send::<Rc<u32>>();
+        assert!(matches!(
+            resources.try_get_non_send_mut::<Rc<u32>>(),
+            Err(EcsError::ResourceBorrowed { .. })
+        ));
+    }
+
+    let mut resources = std::thread::spawn(move || {
+        assert!(matches!(
+            resources.try_get_non_send::<Rc<u32>>().map(|_| ()),
+            Err(EcsError::NonSendAccess { .. })
+        ));
+        resources
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(resources.remove_non_send::<Rc<u32>>().as_deref(), Some(&2));
+    assert!(!resources.contains_non_send::<Rc<u32>>());
+}
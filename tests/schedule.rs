@@ -0,0 +1,87 @@
+use ecs::error::EcsError;
+use ecs::resource::{ReadNonSend, ResourceSet, Resources, Write};
+use ecs::schedule::{DynSchedule, Schedule};
+use ecs::system::{QuerySet, System};
+use ecs::world::World;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct Ticks(u32);
+
+struct Tick;
+
+impl System for Tick {
+    type Resources = Write<Ticks>;
+    type Queries = ();
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, mut ticks: <Self::Resources as ResourceSet>::Result) {
+        ticks.0 += 1;
+    }
+}
+
+struct Window;
+
+impl System for Window {
+    type Resources = ReadNonSend<Rc<u32>>;
+    type Queries = ();
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, window: <Self::Resources as ResourceSet>::Result) {
+        assert_eq!(**window, 7);
+    }
+}
+
+fn resources() -> Resources {
+    let mut resources = Resources::default();
+
+    resources.insert(Ticks::default());
+    resources.insert_non_send(Rc::new(7u32));
+    resources
+}
+
+#[test]
+fn thread_local_systems_run_on_the_owner_thread() {
+    let mut world = World::default();
+    let mut resources = resources();
+
+    Schedule::new()
+        .with_system(Tick)
+        .with_system(Window)
+        .finish()
+        .run(&mut world, &mut resources);
+    DynSchedule::new()
+        .with_system(Tick)
+        .with_system(Window)
+        .run(&mut world, &mut resources);
+
+    assert_eq!(resources.get::<Ticks>().0, 2);
+}
+
+#[test]
+fn thread_local_systems_are_rejected_on_other_threads() {
+    let mut world = World::default();
+    let mut resources = resources();
+    let mut schedule = Schedule::new().with_system(Tick).with_system(Window).finish();
+    let mut send_schedule = Schedule::new().with_system(Tick).finish();
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                assert!(matches!(
+                    schedule.try_run(&mut world, &mut resources),
+                    Err(EcsError::ThreadLocalSystem { .. })
+                ));
+                assert!(matches!(
+                    DynSchedule::new()
+                        .with_system(Tick)
+                        .with_system(Window)
+                        .try_run(&mut world, &mut resources),
+                    Err(EcsError::ThreadLocalSystem { .. })
+                ));
+                send_schedule.run(&mut world, &mut resources);
+            })
+            .join()
+            .unwrap()
+    });
+
+    assert_eq!(resources.get::<Ticks>().0, 1);
+}
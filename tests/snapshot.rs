@@ -0,0 +1,109 @@
+mod common;
+
+use common::{ChildOf, Pos, Vel};
+use ecs::component::Component;
+use ecs::error::EcsError;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::relation::Relations;
+use ecs::replication::{DeltaDecoder, DeltaEncoder, Replicate};
+use ecs::snapshot::SnapshotRing;
+use ecs::world::World;
+
+#[derive(Component, Debug, PartialEq)]
+struct Opaque(u32);
+
+impl Replicate for Pos {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out)
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        u32::decode(bytes).map(Pos)
+    }
+}
+
+#[test]
+fn rollback_restores_components_and_entities() {
+    let mut world = common::world();
+    let mut ring = SnapshotRing::new(3);
+    let a = world.create((Pos(1), Vel(1)));
+
+    ring.record(0, &mut world).unwrap();
+
+    for (pos, vel) in <(Write<Pos>, Read<Vel>)>::query().iter_mut(&mut world) {
+        pos.0 += vel.0;
+    }
+
+    let b = world.create((Pos(9),));
+    world.remove(a);
+
+    assert!(ring.rollback(0, &mut world));
+    assert!(world.contains(a));
+    assert!(!world.contains(b));
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(1)));
+}
+
+#[test]
+fn restore_marks_columns_changed() {
+    let mut world = common::world();
+    let a = world.create((Pos(1),));
+    world.create((Pos(2),));
+    let mut sorted = Read::<Pos>::query().sorted_by_key(|pos: &Pos| pos.0);
+    let snapshot = world.snapshot().unwrap();
+
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 2]);
+    Write::<Pos>::query().get_mut(&mut world, a).unwrap().0 = 3;
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![2, 3]);
+
+    world.restore(&snapshot);
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn restore_is_replicated() {
+    let mut server = World::default();
+    let mut client = World::default();
+    let mut encoder = DeltaEncoder::new();
+    let mut decoder = DeltaDecoder::new();
+
+    server.register_clone::<Pos>();
+    server.register_replicated::<Pos>();
+    client.register_replicated::<Pos>();
+
+    let a = server.create((Pos(1),));
+    let snapshot = server.snapshot().unwrap();
+
+    decoder.apply(&mut client, &encoder.encode(&server)).unwrap();
+    Write::<Pos>::query().get_mut(&mut server, a).unwrap().0 = 5;
+    decoder.apply(&mut client, &encoder.encode(&server)).unwrap();
+    assert_eq!(Read::<Pos>::query().get(&client, decoder.get(a).unwrap()), Some(&Pos(5)));
+
+    server.restore(&snapshot);
+    decoder.apply(&mut client, &encoder.encode(&server)).unwrap();
+    assert_eq!(Read::<Pos>::query().get(&client, decoder.get(a).unwrap()), Some(&Pos(1)));
+}
+
+#[test]
+fn snapshot_of_uncloneable_component_is_an_error() {
+    let mut world = common::world();
+
+    world.create((Opaque(1),));
+    assert!(matches!(world.snapshot(), Err(EcsError::NotCloneable { .. })));
+}
+
+#[test]
+fn snapshot_relations() {
+    let mut world = common::world();
+    let parent = world.create((Pos(0),));
+    let child = world.create((Pos(1),));
+
+    world.add_relation(child, ChildOf, parent);
+    assert!(matches!(world.snapshot(), Err(EcsError::NotCloneable { .. })));
+
+    world.register_clone::<Relations<ChildOf>>();
+    let snapshot = world.snapshot().unwrap();
+
+    world.remove_relation::<ChildOf>(child, parent);
+    world.restore(&snapshot);
+    assert!(world.relations::<ChildOf>(child).is_some_and(|r| r.contains(parent)));
+}
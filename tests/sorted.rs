@@ -0,0 +1,55 @@
+use ecs::component::Component;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(i32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Tag;
+
+#[test]
+fn iter_sorted_by_key() {
+    let mut world = World::default();
+
+    world.create((Pos(3),));
+    world.create((Pos(1), Tag));
+    world.create((Pos(2),));
+
+    let values = Read::<Pos>::query()
+        .iter_sorted_by_key(&world, |pos| pos.0)
+        .map(|pos| pos.0)
+        .collect::<Vec<_>>();
+
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[test]
+fn sorted_query_tracks_changes() {
+    let mut world = World::default();
+    let mut sorted = Read::<Pos>::query().sorted_by_key(|pos: &Pos| pos.0);
+
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2),));
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 2]);
+
+    Write::<Pos>::query().get_mut(&mut world, a).unwrap().0 = 5;
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![2, 5]);
+
+    world.remove(b);
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![5]);
+}
+
+#[test]
+fn sorted_query_sees_new_archetypes() {
+    let mut world = World::default();
+    let mut sorted = Read::<Pos>::query().sorted_by_key(|pos: &Pos| pos.0);
+
+    world.create((Pos(1),));
+    world.create((Pos(3),));
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 3]);
+
+    world.create((Pos(2), Tag));
+    assert_eq!(sorted.iter(&world).map(|pos| pos.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(sorted.len(), 3);
+}
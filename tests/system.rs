@@ -0,0 +1,114 @@
+use ecs::component::Component;
+use ecs::entity::Entity;
+use ecs::error::EcsError;
+use ecs::query::{EntityRef, IntoQuery, Query, Read, Write};
+use ecs::resource::Resources;
+use ecs::schedule::{DynSchedule, Schedule};
+use ecs::system::{ParamSystem, QuerySet, System, SystemParam, SystemQuery};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Pos(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Vel(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Player;
+
+struct Swap(Entity, Entity);
+
+impl System for Swap {
+    type Resources = ();
+    type Queries = (Write<Pos>, (Read<Player>, Write<Vel>));
+
+    fn run(&mut self, (mut all, mut player): <Self::Queries as QuerySet>::Result, _: ()) {
+        {
+            let [a, b] = all.get_many_mut([self.0, self.1]).unwrap();
+            std::mem::swap(a, b);
+        }
+
+        assert!(all.try_get_many_mut([self.0, self.0]).is_err());
+
+        player.single_mut().unwrap().1 .0 += 10;
+        player.single_mut().unwrap().1 .0 += 10;
+    }
+}
+
+#[test]
+fn system_query_mutable_access() {
+    let mut world = World::default();
+    let mut resources = Resources::default();
+    let a = world.create((Pos(1),));
+    let b = world.create((Pos(2), Vel(1), Player));
+
+    Schedule::new()
+        .with_system(Swap(a, b))
+        .finish()
+        .run(&mut world, &mut resources);
+
+    assert_eq!(Read::<Pos>::query().get(&world, a), Some(&Pos(2)));
+    assert_eq!(Read::<Pos>::query().get(&world, b), Some(&Pos(1)));
+    assert_eq!(Read::<Vel>::query().get(&world, b), Some(&Vel(21)));
+}
+
+struct Inspect;
+
+impl System for Inspect {
+    type Resources = ();
+    type Queries = (EntityRef<'static>, Write<Pos>);
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, _: ()) {}
+}
+
+struct Readers;
+
+impl System for Readers {
+    type Resources = ();
+    type Queries = (EntityRef<'static>, Read<Pos>, (Entity, Read<Pos>));
+
+    fn run(&mut self, _: <Self::Queries as QuerySet>::Result, _: ()) {}
+}
+
+#[derive(SystemParam)]
+struct Movement<'world> {
+    positions: SystemQuery<'world, Write<Pos>>,
+    players: SystemQuery<'world, (Read<Player>, Read<Pos>)>,
+}
+
+#[derive(SystemParam)]
+struct Players<'world> {
+    players: SystemQuery<'world, (Entity, Read<Player>)>,
+    positions: SystemQuery<'world, Write<Pos>>,
+}
+
+#[test]
+fn conflicting_queries_in_a_system_are_rejected() {
+    assert!(matches!(
+        Schedule::new().try_with_system(Inspect),
+        Err(EcsError::ConflictingAccess { .. })
+    ));
+    assert!(matches!(
+        DynSchedule::new().try_with_system(Inspect),
+        Err(EcsError::ConflictingAccess { .. })
+    ));
+    assert!(Schedule::new().try_with_system(Readers).is_ok());
+}
+
+#[test]
+fn conflicting_system_params_are_rejected() {
+    assert!(matches!(
+        ParamSystem::<Movement, _>::try_new(|_| {}),
+        Err(EcsError::ConflictingAccess { .. })
+    ));
+    assert!(ParamSystem::<Players, _>::try_new(|_| {}).is_ok());
+}
+
+#[test]
+fn conflicting_fetches_in_a_query_are_rejected() {
+    let err = Query::<<(Read<Pos>, Write<Pos>) as IntoQuery>::Fetch>::try_new().err().unwrap();
+
+    assert!(matches!(&err, EcsError::ConflictingAccess { component } if component == "system::Pos"));
+    assert_eq!(err.to_string(), "conflicting access to component `system::Pos` in system");
+    assert!(Query::<<(Entity, Write<Pos>) as IntoQuery>::Fetch>::try_new().is_ok());
+}
@@ -0,0 +1,130 @@
+use ecs::component::Component;
+use ecs::error::EcsError;
+use ecs::filter;
+use ecs::query::{IntoQuery, Read, Write};
+use ecs::world::World;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+#[component(storage = "single")]
+struct Player(u32);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct Name(&'static str);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+struct ChildOf;
+
+#[test]
+fn move_entity_to_full_world_keeps_relations() {
+    let mut world = World::default();
+    let mut other = World::default();
+    let parent = world.create((Name("parent"),));
+    let child = world.create((Player(1),));
+
+    world.add_relation(child, ChildOf, parent);
+    other.create((Player(2),));
+
+    assert!(matches!(
+        world.move_entity_to(&mut other, child),
+        Err(EcsError::StorageFull { .. })
+    ));
+    assert!(world.relations::<ChildOf>(child).is_some_and(|r| r.contains(parent)));
+    assert_eq!(world.sources::<ChildOf>(parent).collect::<Vec<_>>(), vec![child]);
+    assert_eq!(Read::<Player>::query().iter(&other).count(), 1);
+}
+
+#[test]
+fn move_entity_to_severs_relations() {
+    let mut world = World::default();
+    let mut other = World::default();
+    let parent = world.create((Name("parent"),));
+    let child = world.create((Player(1),));
+
+    world.add_relation(child, ChildOf, parent);
+
+    let moved = world.move_entity_to(&mut other, child).unwrap();
+
+    assert!(!world.contains(child));
+    assert_eq!(world.sources::<ChildOf>(parent).count(), 0);
+    assert!(other.relations::<ChildOf>(moved).is_none());
+    assert_eq!(Read::<Player>::query().get(&other, moved), Some(&Player(1)));
+}
+
+#[test]
+fn create_with_id_into_full_storage_keeps_the_old_entity() {
+    let mut world = World::default();
+    let player = world.create((Player(1),));
+    let named = world.create((Name("a"),));
+
+    assert!(matches!(
+        world.try_create_with_id(named, (Player(2),)),
+        Err(EcsError::StorageFull { .. })
+    ));
+    assert_eq!(Read::<Name>::query().get(&world, named), Some(&Name("a")));
+
+    world.try_create_with_id(player, (Player(3),)).unwrap();
+    assert_eq!(Read::<Player>::query().get(&world, player), Some(&Player(3)));
+}
+
+#[test]
+fn split_world_is_processed_on_another_thread() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<World>();
+
+    let mut world = World::default();
+    let parent = world.create((Name("parent"),));
+    let child = world.create((Name("child"), Player(1)));
+
+    world.add_relation(child, ChildOf, parent);
+
+    let mut split = world.split(&filter::Component::<Player>::default());
+
+    split = std::thread::spawn(move || {
+        for player in Write::<Player>::query().iter_mut(&mut split) {
+            player.0 += 1;
+        }
+
+        split
+    })
+    .join()
+    .unwrap();
+
+    let entities = world.merge(split).unwrap();
+    let child = entities.values().copied().next().unwrap();
+
+    assert_eq!(entities.len(), 1);
+    assert_eq!(Read::<Player>::query().get(&world, child), Some(&Player(2)));
+    assert_eq!(Read::<Name>::query().get(&world, child), Some(&Name("child")));
+}
+
+#[test]
+fn merge_remaps_entities_and_relations() {
+    let mut world = World::default();
+    let mut other = World::default();
+    let existing = world.create((Name("existing"),));
+    let parent = other.create((Name("parent"),));
+    let child = other.create((Name("child"),));
+
+    other.add_relation(child, ChildOf, parent);
+
+    let entities = world.merge(other).unwrap();
+    let (parent, child) = (entities[&parent], entities[&child]);
+
+    assert!(world.contains(existing));
+    assert_eq!(Read::<Name>::query().get(&world, child), Some(&Name("child")));
+    assert_eq!(world.sources::<ChildOf>(parent).collect::<Vec<_>>(), vec![child]);
+    assert!(world.relations::<ChildOf>(child).is_some_and(|r| r.contains(parent)));
+}
+
+#[test]
+fn merge_into_full_storage_is_an_error() {
+    let mut world = World::default();
+    let mut other = World::default();
+
+    world.create((Player(1),));
+    other.create((Player(2),));
+
+    assert!(matches!(world.merge(other), Err(EcsError::StorageFull { .. })));
+    assert_eq!(Read::<Player>::query().iter(&world).collect::<Vec<_>>(), vec![&Player(1)]);
+}